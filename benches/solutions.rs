@@ -0,0 +1,41 @@
+//! Benchmarks each registered day's parsing and both parts separately, against the puzzle inputs
+//! in `data/`.
+//!
+//! Run a single day with e.g. `cargo bench -- day11/`.
+
+use aoc2020::input::{DataDir, InputProvider as _};
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+
+fn bench_solutions(c: &mut Criterion) {
+    let provider = DataDir::default();
+    for solution in aoc2020::DAYS {
+        let day = solution.day();
+        let buffered = match provider.lines(day).and_then(|lines| lines.buffer()) {
+            Ok(buffered) => buffered,
+            Err(err) => {
+                eprintln!("Skipping day {}: {}", day, err);
+                continue;
+            }
+        };
+        let input = solution
+            .parse(buffered.lines())
+            .unwrap_or_else(|err| panic!("Day {}: {}", day, err));
+
+        let mut group = c.benchmark_group(format!("day{}", day));
+        // Some days take seconds per iteration, keep the total run time bearable
+        group.sample_size(10);
+        group.bench_function("parse", |b| {
+            b.iter_batched(
+                || buffered.lines(),
+                |lines| solution.parse(lines),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_function("part1", |b| b.iter(|| input.part1()));
+        group.bench_function("part2", |b| b.iter(|| input.part2()));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_solutions);
+criterion_main!(benches);
@@ -1,21 +1,37 @@
-fn load_data() -> Vec<usize> {
-    crate::util::data_lines(1).map(|s| <usize as std::str::FromStr>::from_str(&s)).map(Result::unwrap).collect()
-}
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
 
-pub fn part1() {
-    let data = load_data();
-    let (a, b) = crate::util::find_pair(&data, 2020).unwrap();
-    println!("Day 1, Part 1: {}", a * b);
-}
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: usize = 1;
+
+    type Input = Vec<usize>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| line.parse_as())
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        let (a, b) = crate::util::find_pair(data, 2020).expect("No two entries sum to 2020");
+        (a * b).into()
+    }
 
-pub fn part2() {
-    let data = load_data();
-    for idx in 0..data.len() - 2 {
-        let first = data[idx];
-        if let Some((second, third)) = crate::util::find_pair(&data[idx + 1..], 2020 - first) {
-            println!("Day 1, Part 2: {}", first * second * third);
-            return;
+    fn part2(data: &Self::Input) -> Answer {
+        for idx in 0..data.len().saturating_sub(2) {
+            let first = data[idx];
+            if let Some((second, third)) = crate::util::find_pair(&data[idx + 1..], 2020 - first) {
+                return (first * second * third).into();
+            }
         }
+        panic!("No three entries sum to 2020");
     }
-    panic!("No result found");
+}
+
+#[test]
+fn test_example() {
+    let data = Day1::parse(crate::input::from_str("1721\n979\n366\n299\n675\n1456")).unwrap();
+    assert_eq!(Day1::part1(&data), 514579);
+    assert_eq!(Day1::part2(&data), 241861950);
 }
@@ -1,36 +1,63 @@
-fn load_data() -> impl Iterator<Item = u128> {
-    crate::util::data_lines(10)
-        .map(|line| std::str::FromStr::from_str(&line))
-        .map(Result::unwrap)
-}
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: usize = 10;
 
-pub fn part1() {
-    let mut data = load_data().collect::<Vec<_>>();
-    data.sort_unstable();
-    let mut counts = [0, 0, 1]; // There's always a 3-gap between device and highest converter
-    counts[(data[0] - 1) as usize] += 1;
-    for window in data.windows(2) {
-        counts[(window[1] - window[0] - 1) as usize] += 1;
+    type Input = Vec<u128>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| line.parse_as())
     }
-    println!("Day 10, Part 1: {}", counts[0] * counts[2]);
-}
 
-pub fn part2() {
-    let mut max = 0;
-    let data = load_data().map(|v| { if v > max { max = v }; v }).collect::<std::collections::HashSet::<_>>();
-    let mut back_one = 1u128;
-    let mut back_two = 0u128;
-    let mut back_three = 0u128;
-    let mut back_none = 0u128;
-    for i in 1..=max {
-        back_none = if data.contains(&i) {
-            back_one + back_two + back_three
-        } else {
-            0
-        };
-        back_three = back_two;
-        back_two = back_one;
-        back_one = back_none;
+    fn part1(data: &Self::Input) -> Answer {
+        let mut data = data.clone();
+        data.sort_unstable();
+        let mut counts = [0, 0, 1]; // There's always a 3-gap between device and highest converter
+        counts[(data[0] - 1) as usize] += 1;
+        for window in data.windows(2) {
+            counts[(window[1] - window[0] - 1) as usize] += 1;
+        }
+        (counts[0] * counts[2]).into()
     }
-    println!("Day 10, Part 2: {}", back_none);
+
+    fn part2(data: &Self::Input) -> Answer {
+        let max = data.iter().copied().max().unwrap_or(0);
+        let data = data.iter().copied().collect::<std::collections::HashSet::<_>>();
+        let mut back_one = 1u128;
+        let mut back_two = 0u128;
+        let mut back_three = 0u128;
+        let mut back_none = 0u128;
+        for i in 1..=max {
+            back_none = if data.contains(&i) {
+                back_one + back_two + back_three
+            } else {
+                0
+            };
+            back_three = back_two;
+            back_two = back_one;
+            back_one = back_none;
+        }
+        back_none.into()
+    }
+}
+
+#[test]
+fn test_example_small() {
+    let data = Day10::parse(crate::input::from_str("16\n10\n15\n5\n1\n11\n7\n19\n6\n12\n4")).unwrap();
+    assert_eq!(Day10::part1(&data), 35);
+    assert_eq!(Day10::part2(&data), 8);
+}
+
+#[test]
+fn test_example_large() {
+    let data = Day10::parse(crate::input::from_str(
+        "28\n33\n18\n42\n31\n14\n46\n20\n48\n47\n24\n23\n49\n45\n19\n38\n39\n11\n1\n32\n25\n35\n8\n17\n7\n9\n4\n2\n34\n10\n3",
+    ))
+    .unwrap();
+    assert_eq!(Day10::part1(&data), 220);
+    assert_eq!(Day10::part2(&data), 19208);
 }
@@ -1,5 +1,10 @@
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::parse::Field;
+
 #[derive(Debug, Copy, Clone)]
-enum Instruction {
+pub enum Instruction {
     Forward(isize),
     North(isize),
     East(isize),
@@ -139,46 +144,67 @@ impl Heading {
     }
 }
 
-fn load_data() -> impl Iterator<Item = Instruction> {
-    crate::util::data_lines(12).map(|line| {
-        let arg = <isize as std::str::FromStr>::from_str(&line[1..]).unwrap();
-        match line.as_bytes()[0] {
-            b'F' => Instruction::Forward(arg),
-            b'N' => Instruction::North(arg),
-            b'E' => Instruction::East(arg),
-            b'S' => Instruction::South(arg),
-            b'W' => Instruction::West(arg),
-            b'R' => match arg {
-                90 => Instruction::Right,
-                180 => Instruction::UTurn,
-                270 => Instruction::Left,
-                _ => unreachable!("Non-ortholinear turn"),
-            }
-            b'L' => match arg {
-                90 => Instruction::Left,
-                180 => Instruction::UTurn,
-                270 => Instruction::Right,
-                _ => unreachable!("Non-ortholinear turn"),
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: usize = 12;
+
+    type Input = Vec<Instruction>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| {
+            let (inst, arg) = Field::new(line).split_at(1)?;
+            let non_ortholinear = || arg.error(format!("non-ortholinear turn of {} degrees", &*arg));
+            let arg = arg.parse()?;
+            match &*inst {
+                "F" => Ok(Instruction::Forward(arg)),
+                "N" => Ok(Instruction::North(arg)),
+                "E" => Ok(Instruction::East(arg)),
+                "S" => Ok(Instruction::South(arg)),
+                "W" => Ok(Instruction::West(arg)),
+                "R" => match arg {
+                    90 => Ok(Instruction::Right),
+                    180 => Ok(Instruction::UTurn),
+                    270 => Ok(Instruction::Left),
+                    _ => Err(non_ortholinear()),
+                }
+                "L" => match arg {
+                    90 => Ok(Instruction::Left),
+                    180 => Ok(Instruction::UTurn),
+                    270 => Ok(Instruction::Right),
+                    _ => Err(non_ortholinear()),
+                }
+                _ => Err(inst.error(format!("unknown instruction '{}'", &*inst))),
             }
-            _ => unreachable!(),
+        })
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        let mut ship = Ship::new();
+        for inst in data {
+            ship.step(*inst);
         }
-    })
-}
+        ship.manhattan_distance().into()
+    }
 
-pub fn part1() {
-    let data = load_data();
-    let mut ship = Ship::new();
-    for inst in data {
-        ship.step(inst);
+    fn part2(data: &Self::Input) -> Answer {
+        let mut ship = Ship2::new();
+        for inst in data {
+            ship.step(*inst);
+        }
+        ship.manhattan_distance().into()
     }
-    println!("Day 12, Part 1: {}", ship.manhattan_distance());
 }
 
-pub fn part2() {
-    let data = load_data().collect::<Vec<_>>();
-    let mut ship = Ship2::new();
-    for inst in data {
-        ship.step(inst);
-    }
-    println!("Day 12, Part 2: {}", ship.manhattan_distance());
+#[test]
+fn test_unknown_instruction() {
+    let err = Day12::parse(crate::input::from_str("F10\nQ3\n")).err().unwrap();
+    assert_eq!(err.to_string(), "<input>:2:1: unknown instruction 'Q'");
+}
+
+#[test]
+fn test_example() {
+    let data = Day12::parse(crate::input::from_str("F10\nN3\nF7\nR90\nF11")).unwrap();
+    assert_eq!(Day12::part1(&data), 25);
+    assert_eq!(Day12::part2(&data), 286);
 }
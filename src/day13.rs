@@ -1,21 +1,25 @@
 use std::collections::HashSet;
 
-pub fn part1() {
-    let mut lines = crate::util::data_lines(13);
-    let time = <usize as std::str::FromStr>::from_str(&lines.next().unwrap()).unwrap();
-    let busses = lines.next().unwrap();
-    let busses: HashSet::<_> = busses.split(',').filter(|bus| *bus != "x").map(<usize as std::str::FromStr>::from_str).map(Result::unwrap).collect();
-    let (id, to_wait) = busses.iter().map(|id| (id, id - (time % id))).min_by_key(|(_id, to_wait)| *to_wait).unwrap();
-    println!("Day 13, Part 1: {}", id * to_wait);
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+/// The bus notes: the earliest time we could depart and the bus IDs in service (`None` for `x`)
+pub struct Notes {
+    time: usize,
+    busses: Vec<Option<usize>>,
 }
 
-// If `extended_euclidean_algorithm(a, b) = (gcd, bezout)` then `gcd = left * a + right * b`
-struct Bezout {
-    left: i128,
-    right: i128,
+/// The coefficients of Bézout's identity: if `extended_euclidean_algorithm(a, b) = (gcd, bezout)`
+/// then `gcd = left * a + right * b`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bezout {
+    pub left: i128,
+    pub right: i128,
 }
 
-fn extended_euclidean_algorithm(a: i128, b: i128) -> (i128, Bezout) {
+/// Find the greatest common divisor of `a` and `b`, along with the Bézout coefficients for them
+pub fn extended_euclidean_algorithm(a: i128, b: i128) -> (i128, Bezout) {
     let remainder = a % b;
     let quotient = a / b;
     if remainder == 0 {
@@ -41,8 +45,10 @@ fn test_eea_240_46() {
     assert!(bezout.right == 47);
 }
 
-// Find the inverse of `x` in `Z_m`
-fn modulus_inverse(x: i128, m: i128) -> i128 {
+/// Find the inverse of `x` in `Z_m`.
+///
+/// Panics if `x` and `m` aren't coprime, since then there's no inverse.
+pub fn modulus_inverse(x: i128, m: i128) -> i128 {
     let (gcd, bezout) = extended_euclidean_algorithm(m, x);
     assert!(gcd == 1);
     assert!(m * bezout.left + x * bezout.right == gcd);
@@ -51,35 +57,63 @@ fn modulus_inverse(x: i128, m: i128) -> i128 {
     ((bezout.right % m) + m) % m
 }
 
-pub fn part2() {
-    let mut lines = crate::util::data_lines(13);
-    let _ = lines.next().unwrap(); // Ignore the first line
-    let busses = lines.next().unwrap();
-    let busses = busses.split(',').map(|id| -> Option<i128> {
-        if id == "x" {
-            None
-        } else {
-            Some(std::str::FromStr::from_str(id).unwrap())
-        }
-    });
-    let mut product_of_moduli = 1;
-    let busses = busses.enumerate().filter_map(|(offset, id)| {
-        match id {
-            Some(id) => {
-                product_of_moduli *= id;
-                let offset = id - offset as i128;
-                Some((offset, id))
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: usize = 13;
+
+    type Input = Notes;
+
+    fn parse(mut lines: Lines) -> Result<Self::Input> {
+        let time = lines.next_line()?.parse_as()?;
+        let line = lines.next_line()?;
+        let busses = line.split(',').map(|id| {
+            if id == "x" {
+                Ok(None)
+            } else {
+                id.parse()
+                    .map(Some)
+                    .map_err(|err| line.error(format!("invalid bus ID '{}': {}", id, err)))
             }
-            None => None
-        }
-    }).collect::<Vec<_>>();
+        }).collect::<Result<_>>()?;
+        Ok(Notes { time, busses })
+    }
 
-    let earliest_time = busses.into_iter().map(move |(offset, id)| {
-        let prod = product_of_moduli / id;
-        prod * offset * modulus_inverse(prod, id)
-    }).sum::<i128>();
+    fn part1(notes: &Self::Input) -> Answer {
+        let time = notes.time;
+        let busses: HashSet::<_> = notes.busses.iter().flatten().collect();
+        let (id, to_wait) = busses.iter().map(|id| (id, *id - (time % *id))).min_by_key(|(_id, to_wait)| *to_wait).expect("No busses are in service");
+        (*id * to_wait).into()
+    }
 
-    let earliest_time = ((earliest_time % product_of_moduli) + product_of_moduli) % product_of_moduli;
+    fn part2(notes: &Self::Input) -> Answer {
+        let busses = notes.busses.iter().map(|id| id.map(|id| id as i128));
+        let mut product_of_moduli = 1;
+        let busses = busses.enumerate().filter_map(|(offset, id)| {
+            match id {
+                Some(id) => {
+                    product_of_moduli *= id;
+                    let offset = id - offset as i128;
+                    Some((offset, id))
+                }
+                None => None
+            }
+        }).collect::<Vec<_>>();
 
-    println!("Day 13, Part 2: {}", earliest_time);
+        let earliest_time = busses.into_iter().map(move |(offset, id)| {
+            let prod = product_of_moduli / id;
+            prod * offset * modulus_inverse(prod, id)
+        }).sum::<i128>();
+
+        let earliest_time = ((earliest_time % product_of_moduli) + product_of_moduli) % product_of_moduli;
+
+        earliest_time.into()
+    }
+}
+
+#[test]
+fn test_example_939() {
+    let input = Day13::parse(crate::input::from_str("939\n7,13,x,x,59,x,31,19")).unwrap();
+    assert_eq!(Day13::part1(&input), 295);
+    assert_eq!(Day13::part2(&input), 1068781);
 }
@@ -1,5 +1,10 @@
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::parse::Field;
+
 #[derive(Debug, Clone, Copy)]
-struct Mask {
+pub struct Mask {
     one: u64,
     zero: u64,
     x: u64,
@@ -16,104 +21,125 @@ impl Mask {
 }
 
 #[derive(Debug)]
-enum Operation {
+pub enum Operation {
     Mask(Mask),
     Write { addr: u64, value: u64 },
 }
 
-fn load_data() -> Vec<Operation> {
-    crate::util::data_lines(14)
-        .map(|line| {
-            match &line[0..3] {
-                "mas" => {
-                    let mask = line.as_bytes()[7..].iter().enumerate().fold(
-                        Mask::new(),
-                        |mut acc, (idx, c)| {
-                            let shift = 35 - idx;
-                            match c {
-                                b'1' => acc.one |= 1u64 << shift,
-                                b'0' => acc.zero |= 1u64 << shift,
-                                b'X' => acc.x |= 1u64 << shift,
-                                _ => unreachable!(),
-                            }
-                            acc
-                        },
-                    );
-                    Operation::Mask(mask)
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: usize = 14;
+
+    type Input = Vec<Operation>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| {
+            let (target, value) = Field::new(line).split_once(" = ")?;
+            if &*target == "mask" {
+                if value.len() != 36 {
+                    return Err(value.error("mask must be 36 bits long"));
                 }
-                "mem" => {
-                    let mut addr = 0;
-                    let mut idx = 4; // Skip over "mem["
-                    loop {
-                        let c = line.as_bytes()[idx];
-                        if c == b']' {
-                            break;
-                        } else {
-                            addr *= 10;
-                            addr += (c - b'0') as u64;
-                            idx += 1;
+                let mask = value.char_indices().try_fold(Mask::new(), |mut acc, (idx, c)| {
+                    let shift = 35 - idx;
+                    match c {
+                        '1' => acc.one |= 1u64 << shift,
+                        '0' => acc.zero |= 1u64 << shift,
+                        'X' => acc.x |= 1u64 << shift,
+                        _ => {
+                            let (_, bit) = value.split_at(idx)?;
+                            return Err(bit.error(format!("unexpected mask bit '{}'", c)));
                         }
                     }
-
-                    let value = std::str::FromStr::from_str(&line[idx + 4..]).unwrap(); // Skip over "] = "
-                    Operation::Write { addr, value }
+                    Ok(acc)
+                })?;
+                Ok(Operation::Mask(mask))
+            } else {
+                let (name, addr) = target.bracketed('[', ']')?;
+                if &*name != "mem" {
+                    return Err(target.error("expected 'mask = ...' or 'mem[...] = ...'"));
                 }
-                _ => unreachable!(),
+                Ok(Operation::Write {
+                    addr: addr.parse()?,
+                    value: value.parse()?,
+                })
             }
         })
-        .collect()
-}
+    }
 
-pub fn part1() {
-    let data = load_data();
-    let mut mem = std::collections::BTreeMap::<u64, u64>::new();
-    let mut mask = Mask::new();
-    for op in data.iter() {
-        match op {
-            Operation::Mask(m) => mask = *m,
-            Operation::Write { addr, value } => {
-                mem.insert(*addr, (*value | mask.one) & !mask.zero);
+    fn part1(data: &Self::Input) -> Answer {
+        let mut mem = std::collections::BTreeMap::<u64, u64>::new();
+        let mut mask = Mask::new();
+        for op in data.iter() {
+            match op {
+                Operation::Mask(m) => mask = *m,
+                Operation::Write { addr, value } => {
+                    mem.insert(*addr, (*value | mask.one) & !mask.zero);
+                }
             }
         }
+        let memory_sum: u64 = mem.values().sum();
+        memory_sum.into()
     }
-    let memory_sum: u64 = mem.iter().map(|(_k, v)| v).sum();
-    println!("Day 14, Part 1: {}", memory_sum);
-}
 
-pub fn part2() {
-    let data = load_data();
-    let mut mem = std::collections::BTreeMap::<u64, u64>::new();
-    let mut mask = Mask::new();
-    for op in data.iter() {
-        match op {
-            Operation::Mask(m) => mask = *m,
-            Operation::Write { addr, value } => {
-               let mut written_addresses = std::collections::BTreeSet::new();
-               written_addresses.insert(0);
-               for idx in 0..36 {
-                   let bit = 1 << idx;
-                   if mask.one & bit == bit {
-                       written_addresses = written_addresses.into_iter().map(|w_addr| {
-                           w_addr | bit
-                       }).collect();
-                   } else if mask.zero & bit == bit {
-                       written_addresses = written_addresses.into_iter().map(|w_addr| {
-                           w_addr | (addr & bit)
-                       }).collect();
-                   } else if mask.x & bit == bit {
-                       written_addresses = written_addresses.into_iter().flat_map(move |w_addr| {
-                           vec![w_addr, w_addr | bit].into_iter()
-                       }).collect();
-                   } else {
-                       unreachable!("Bit not in one/zero/x");
+    fn part2(data: &Self::Input) -> Answer {
+        let mut mem = std::collections::BTreeMap::<u64, u64>::new();
+        let mut mask = Mask::new();
+        for op in data.iter() {
+            match op {
+                Operation::Mask(m) => mask = *m,
+                Operation::Write { addr, value } => {
+                   let mut written_addresses = std::collections::BTreeSet::new();
+                   written_addresses.insert(0);
+                   for idx in 0..36 {
+                       let bit = 1 << idx;
+                       if mask.one & bit == bit {
+                           written_addresses = written_addresses.into_iter().map(|w_addr| {
+                               w_addr | bit
+                           }).collect();
+                       } else if mask.zero & bit == bit {
+                           written_addresses = written_addresses.into_iter().map(|w_addr| {
+                               w_addr | (addr & bit)
+                           }).collect();
+                       } else if mask.x & bit == bit {
+                           written_addresses = written_addresses.into_iter().flat_map(move |w_addr| {
+                               vec![w_addr, w_addr | bit].into_iter()
+                           }).collect();
+                       } else {
+                           unreachable!("Bit not in one/zero/x");
+                       }
+                   }
+                   for addr in written_addresses {
+                       mem.insert(addr, *value);
                    }
-               }
-               for addr in written_addresses {
-                   mem.insert(addr, *value);
-               }
+                }
             }
         }
+        let memory_sum: u64 = mem.values().sum();
+        memory_sum.into()
     }
-    let memory_sum: u64 = mem.iter().map(|(_k, v)| v).sum();
-    println!("Day 14, Part 2: {}", memory_sum);
+}
+
+#[test]
+fn test_example_part1() {
+    let data = Day14::parse(crate::input::from_str(
+        "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0",
+    ))
+    .unwrap();
+    assert_eq!(Day14::part1(&data), 165);
+}
+
+#[test]
+fn test_example_part2() {
+    let data = Day14::parse(crate::input::from_str(
+        "mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1",
+    ))
+    .unwrap();
+    assert_eq!(Day14::part2(&data), 208);
 }
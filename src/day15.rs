@@ -1,32 +1,75 @@
-fn load_data() -> (usize, std::collections::HashMap<usize, usize>) {
-    let mut data = std::collections::HashMap::new();
-    data.insert(0, 1);
-    data.insert(1, 2);
-    data.insert(4, 3);
-    data.insert(13, 4);
-    data.insert(15, 5);
-    data.insert(12, 6);
-    data.insert(16, 7);
-    (0, data)
-}
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
 
-fn play_the_game(turns: usize) -> usize {
-    let (mut next, mut seen) = load_data();
-    for turn in 8..turns {
-        let tmp_next = match seen.get(&next) {
-            Some(prev_turn) => turn - prev_turn,
-            None => 0,
+/// The number spoken on the given turn.
+///
+/// When each number was last spoken is kept in a flat array indexed by the number, which is much
+/// faster than a `HashMap` for the tens of millions of turns in part 2.  No number can be larger
+/// than the number of turns (or the largest starting number), so the array never needs to grow.
+fn play_the_game(seeds: &[usize], turns: usize) -> usize {
+    let (last, earlier) = seeds.split_last().expect("No starting numbers");
+    let size = seeds.iter().copied().max().unwrap_or(0).max(turns) + 1;
+    // The turn (counting from 1) each number was last spoken on, or 0 if it hasn't been yet
+    let mut last_spoken = vec![0u32; size];
+    for (idx, seed) in earlier.iter().enumerate() {
+        last_spoken[*seed] = idx as u32 + 1;
+    }
+    let mut next = *last;
+    for turn in seeds.len()..turns {
+        let previous = std::mem::replace(&mut last_spoken[next], turn as u32);
+        next = match previous {
+            0 => 0,
+            previous => turn - previous as usize,
         };
-        seen.insert(next, turn);
-        next = tmp_next;
     }
     next
 }
 
-pub fn part1() {
-    println!("Day 15, Part 1: {}", play_the_game(2020));
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: usize = 15;
+
+    type Input = Vec<usize>;
+
+    fn parse(mut lines: Lines) -> Result<Self::Input> {
+        let line = lines.next_line()?;
+        line.split(',')
+            .map(|seed| {
+                seed.parse()
+                    .map_err(|err| line.error(format!("invalid starting number '{}': {}", seed, err)))
+            })
+            .collect()
+    }
+
+    fn part1(seeds: &Self::Input) -> Answer {
+        play_the_game(seeds, 2020).into()
+    }
+
+    fn part2(seeds: &Self::Input) -> Answer {
+        play_the_game(seeds, 30_000_000).into()
+    }
+}
+
+#[test]
+fn test_example_part1() {
+    for (seeds, expected) in [
+        ("0,3,6", 436),
+        ("1,3,2", 1),
+        ("2,1,3", 10),
+        ("1,2,3", 27),
+        ("2,3,1", 78),
+        ("3,2,1", 438),
+        ("3,1,2", 1836),
+    ] {
+        let seeds = Day15::parse(crate::input::from_str(seeds)).unwrap();
+        assert_eq!(Day15::part1(&seeds), expected);
+    }
 }
 
-pub fn part2() {
-    println!("Day 15, Part 2: {}", play_the_game(30_000_000));
+#[test]
+fn test_example_part2() {
+    let seeds = Day15::parse(crate::input::from_str("0,3,6")).unwrap();
+    assert_eq!(Day15::part2(&seeds), 175594);
 }
@@ -1,13 +1,25 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::ops::RangeInclusive;
 
+use crate::error::Result;
+use crate::input::{Line, Lines};
+use crate::solution::{Answer, Solution};
+use crate::util::assign_unique;
+
+/// The rules for each field, our ticket and the nearby tickets
+pub struct Notes {
+    rules: Vec<Rule>,
+    my_ticket: Ticket,
+    nearby_tickets: Vec<Ticket>,
+}
+
 #[derive(Debug, PartialEq, Eq, Hash)]
-struct Ticket {
-    fields: [usize; 20],
+pub struct Ticket {
+    fields: Vec<usize>,
 }
 
 impl Ticket {
-    fn find_invalid_field(&self, rules: &HashSet<Rule>) -> Option<usize> {
+    fn find_invalid_field(&self, rules: &[Rule]) -> Option<usize> {
         for field in &self.fields {
             if !rules.iter().any(|rule| rule.accepts(field)) {
                 return Some(*field);
@@ -19,205 +31,192 @@ impl Ticket {
 
 #[derive(Debug, PartialEq, Eq, Hash)]
 struct Rule {
-    name: &'static str,
+    name: String,
     low_range: RangeInclusive<usize>,
     high_range: RangeInclusive<usize>,
 }
 
 impl Rule {
+    /// Parse a rule like `class: 1-3 or 5-7`
+    fn parse(line: &Line) -> Result<Self> {
+        let range = |range: &str| {
+            let (low, high) = range
+                .split_once('-')
+                .ok_or_else(|| line.error(format!("invalid range '{}'", range)))?;
+            let bound = |bound: &str| {
+                bound
+                    .parse::<usize>()
+                    .map_err(|err| line.error(format!("invalid bound '{}': {}", bound, err)))
+            };
+            Ok(bound(low)?..=bound(high)?)
+        };
+        let (name, ranges) = line
+            .split_once(": ")
+            .ok_or_else(|| line.error("expected a rule of the form 'name: A-B or C-D'"))?;
+        let (low_range, high_range) = ranges
+            .split_once(" or ")
+            .ok_or_else(|| line.error("expected two ranges separated by 'or'"))?;
+        Ok(Rule {
+            name: name.to_string(),
+            low_range: range(low_range)?,
+            high_range: range(high_range)?,
+        })
+    }
+
     fn accepts(&self, field: &usize) -> bool {
         self.low_range.contains(field) || self.high_range.contains(field)
     }
 }
 
-fn rules() -> HashSet<Rule> {
-    let mut rules = HashSet::new();
-    rules.insert(Rule {
-        name: "departure location",
-        low_range: 26..=724,
-        high_range: 743..=964,
-    });
-    rules.insert(Rule {
-        name: "departure station",
-        low_range: 33..=845,
-        high_range: 864..=954,
-    });
-    rules.insert(Rule {
-        name: "departure platform",
-        low_range: 26..=472,
-        high_range: 482..=967,
-    });
-    rules.insert(Rule {
-        name: "departure track",
-        low_range: 27..=140,
-        high_range: 158..=956,
-    });
-    rules.insert(Rule {
-        name: "departure date",
-        low_range: 25..=884,
-        high_range: 894..=952,
-    });
-    rules.insert(Rule {
-        name: "departure time",
-        low_range: 37..=924,
-        high_range: 941..=949,
-    });
-    rules.insert(Rule {
-        name: "arrival location",
-        low_range: 48..=311,
-        high_range: 335..=972,
-    });
-    rules.insert(Rule {
-        name: "arrival station",
-        low_range: 39..=703,
-        high_range: 724..=950,
-    });
-    rules.insert(Rule {
-        name: "arrival platform",
-        low_range: 40..=108,
-        high_range: 114..=950,
-    });
-    rules.insert(Rule {
-        name: "arrival track",
-        low_range: 30..=101,
-        high_range: 108..=967,
-    });
-    rules.insert(Rule {
-        name: "class",
-        low_range: 33..=386,
-        high_range: 399..=949,
-    });
-    rules.insert(Rule {
-        name: "duration",
-        low_range: 44..=444,
-        high_range: 452..=956,
-    });
-    rules.insert(Rule {
-        name: "price",
-        low_range: 27..=220,
-        high_range: 234..=974,
-    });
-    rules.insert(Rule {
-        name: "route",
-        low_range: 42..=774,
-        high_range: 790..=959,
-    });
-    rules.insert(Rule {
-        name: "row",
-        low_range: 48..=900,
-        high_range: 918..=956,
-    });
-    rules.insert(Rule {
-        name: "seat",
-        low_range: 42..=165,
-        high_range: 178..=949,
-    });
-    rules.insert(Rule {
-        name: "train",
-        low_range: 45..=831,
-        high_range: 842..=965,
-    });
-    rules.insert(Rule {
-        name: "type",
-        low_range: 49..=522,
-        high_range: 548..=974,
-    });
-    rules.insert(Rule {
-        name: "wagon",
-        low_range: 32..=565,
-        high_range: 588..=964,
-    });
-    rules.insert(Rule {
-        name: "zone",
-        low_range: 27..=608,
-        high_range: 617..=953,
-    });
-    rules
-}
+pub struct Day16;
 
-fn my_ticket() -> Ticket {
-    Ticket {
-        fields: [
-            101, 71, 193, 97, 131, 179, 73, 53, 79, 67, 181, 89, 191, 137, 163, 83, 139, 127, 59,
-            61,
-        ],
+/// Parse a ticket's comma-separated fields, checking there's one per rule
+fn parse_ticket(line: &Line, rules: &[Rule]) -> Result<Ticket> {
+    let fields = line
+        .split(',')
+        .map(|entry| {
+            entry
+                .parse()
+                .map_err(|err| line.error(format!("invalid field '{}': {}", entry, err)))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if fields.len() != rules.len() {
+        return Err(line.error(format!(
+            "expected {} fields, found {}",
+            rules.len(),
+            fields.len()
+        )));
     }
+    Ok(Ticket { fields })
 }
 
-fn other_tickets() -> HashSet<Ticket> {
-    use std::convert::TryInto as _;
-    crate::util::data_lines(16)
-        .map(|line| Ticket {
-            fields: line
-                .split(',')
-                .map(|entry| std::str::FromStr::from_str(entry).unwrap())
-                .collect::<Vec<_>>()
-                .try_into()
-                .unwrap(),
-        })
-        .collect()
+/// Expect the next line to be the given section header
+fn expect_header(lines: &mut Lines, header: &str) -> Result<()> {
+    let line = lines.next_line()?;
+    if &*line == header {
+        Ok(())
+    } else {
+        Err(line.error(format!("expected '{}'", header)))
+    }
 }
 
-pub fn part1() {
-    let rules = rules();
-    let other_tickets = other_tickets();
-    let invalid_fields = other_tickets
+/// Work out which field each rule applies to, by name
+fn assign_fields(notes: &Notes) -> HashMap<&str, usize> {
+    let rules = &notes.rules;
+    let other_tickets = notes
+        .nearby_tickets
+        .iter()
+        .filter(|ticket| ticket.find_invalid_field(rules).is_none())
+        .collect::<Vec<_>>();
+
+    // Work out which fields could be controlled by a rule (i.e. other_tickets contains no counter-examples)
+    let valid_fields_by_rule = rules
         .iter()
-        .flat_map(|ticket| ticket.find_invalid_field(&rules));
-    println!("Day 16, Part 1: {}", invalid_fields.sum::<usize>());
+        .map(|rule| {
+            let fields = (0..rules.len())
+                .filter(|field| {
+                    other_tickets
+                        .iter()
+                        .all(|ticket| rule.accepts(&ticket.fields[*field]))
+                })
+                .collect();
+            (rule.name.as_str(), fields)
+        })
+        .collect();
+
+    // This assumes there's a unique solution
+    assign_unique(valid_fields_by_rule).expect("The fields can't be told apart")
 }
 
-pub fn part2() {
-    let my_ticket = my_ticket();
-    let rules = rules();
-    let other_tickets = other_tickets()
-        .into_iter()
-        .filter(|ticket| ticket.find_invalid_field(&rules).is_none())
-        .collect::<HashSet<_>>();
+impl Solution for Day16 {
+    const DAY: usize = 16;
 
-    // Work out which fields could be controlled by a rule (i.e. other_tickets contains no counter-examples)
-    let mut valid_fields_by_rule = std::collections::HashMap::<&'static str, Vec<usize>>::new();
-    for field in 0..20 {
-        for rule in &rules {
-            if other_tickets
-                .iter()
-                .all(|ticket| rule.accepts(&ticket.fields[field]))
-            {
-                valid_fields_by_rule
-                    .entry(rule.name)
-                    .or_default()
-                    .push(field);
+    type Input = Notes;
+
+    fn parse(mut lines: Lines) -> Result<Self::Input> {
+        let mut rules = Vec::new();
+        loop {
+            let line = lines.next_line()?;
+            if line.is_empty() {
+                break;
             }
+            rules.push(Rule::parse(&line)?);
         }
-    }
 
-    // Naively assign each rule to a field if that's the only field that (a) matches the rule and
-    // (b) has not already been assigned to another rule.
-    //
-    // This assumes there's a unique solution.
-    let rule_names = rules.iter().map(|rule| rule.name).collect::<Vec<_>>();
-    let mut assigned_fields = std::collections::HashSet::<usize>::new();
-    let mut rule_to_field = std::collections::HashMap::<&'static str, usize>::new();
-    while assigned_fields.len() != 20 {
-        for name in &rule_names {
-            let mut possible_rules = valid_fields_by_rule[name]
-                .iter()
-                .filter(|field| !assigned_fields.contains(field));
-            if let (Some(field), None) = (possible_rules.next(), possible_rules.next()) {
-                assigned_fields.insert(*field);
-                rule_to_field.insert(name, *field);
-            }
+        expect_header(&mut lines, "your ticket:")?;
+        let my_ticket = parse_ticket(&lines.next_line()?, &rules)?;
+        if !lines.next_line()?.is_empty() {
+            return Err(lines.error("expected a blank line after our ticket"));
         }
+
+        expect_header(&mut lines, "nearby tickets:")?;
+        let nearby_tickets = lines.parse_each(|line| parse_ticket(line, &rules))?;
+        Ok(Notes {
+            rules,
+            my_ticket,
+            nearby_tickets,
+        })
     }
 
-    // Calculate the final answer
-    let mut product = 1;
-    for name in rule_names {
-        if name.starts_with("departure") {
-            let field = rule_to_field[name];
-            product *= my_ticket.fields[field];
-        }
+    fn part1(notes: &Self::Input) -> Answer {
+        let invalid_fields = notes
+            .nearby_tickets
+            .iter()
+            .flat_map(|ticket| ticket.find_invalid_field(&notes.rules));
+        invalid_fields.sum::<usize>().into()
+    }
+
+    fn part2(notes: &Self::Input) -> Answer {
+        let rule_to_field = assign_fields(notes);
+        rule_to_field
+            .iter()
+            .filter(|(name, _field)| name.starts_with("departure"))
+            .map(|(_name, field)| notes.my_ticket.fields[*field])
+            .product::<usize>()
+            .into()
     }
+}
+
+#[test]
+fn test_example_part1() {
+    let notes = Day16::parse(crate::input::from_str(
+        "class: 1-3 or 5-7
+row: 6-11 or 33-44
+seat: 13-40 or 45-50
+
+your ticket:
+7,1,14
+
+nearby tickets:
+7,3,47
+40,4,50
+55,2,20
+38,6,12",
+    ))
+    .unwrap();
+    assert_eq!(Day16::part1(&notes), 71);
+}
+
+#[test]
+fn test_example_part2() {
+    // There are no "departure" fields in the example, so check how the fields were assigned
+    let notes = Day16::parse(crate::input::from_str(
+        "class: 0-1 or 4-19
+row: 0-5 or 8-19
+seat: 0-13 or 16-19
+
+your ticket:
+11,12,13
 
-    println!("Day 16, Part 2: {}", product);
+nearby tickets:
+3,9,18
+15,1,5
+5,14,9",
+    ))
+    .unwrap();
+    let rule_to_field = assign_fields(&notes);
+    assert_eq!(notes.my_ticket.fields[rule_to_field["class"]], 12);
+    assert_eq!(notes.my_ticket.fields[rule_to_field["row"]], 11);
+    assert_eq!(notes.my_ticket.fields[rule_to_field["seat"]], 13);
+    assert_eq!(Day16::part2(&notes), 1);
 }
@@ -1,7 +1,13 @@
 use std::collections::HashMap;
 
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::grid::Grid;
+
+/// Conway's Game of Life, played in any number of dimensions (as determined by the coordinate type)
 #[derive(Debug)]
-struct Automaton<C: Coordinate> {
+pub struct Automaton<C: Coordinate> {
     cells: HashMap<C, Cell>,
 }
 
@@ -11,7 +17,8 @@ enum Cell {
     Inactive,
 }
 
-trait Coordinate: std::hash::Hash + PartialEq + Eq + Clone + Copy {
+/// A point in the space an `Automaton` lives in
+pub trait Coordinate: std::hash::Hash + PartialEq + Eq + Clone + Copy {
     /// Expand a 2D coordinate to this coordinate system
     fn from_x_y(x: isize, y: isize) -> Self;
     /// I can't be bothered to `impl Add<Self> for (A, B, C)` due to orphan rules getting in the
@@ -73,25 +80,12 @@ impl Coordinate for (isize, isize, isize, isize) {
 }
 
 impl<C: Coordinate> Automaton<C> {
-    fn load_from_data() -> Self {
+    /// Build an automaton from the active cells of its initial 2D slice
+    pub fn from_slice(active: &[(isize, isize)]) -> Self {
         Self {
-            cells: crate::util::data_lines(17)
-                .enumerate()
-                .flat_map(move |(y, line)| {
-                    line.into_bytes()
-                        .into_iter()
-                        .enumerate()
-                        .map(move |(x, byte)| {
-                            (
-                                C::from_x_y(x as isize, y as isize),
-                                if byte == b'#' {
-                                    Cell::Active
-                                } else {
-                                    Cell::Inactive
-                                },
-                            )
-                        })
-                })
+            cells: active
+                .iter()
+                .map(|(x, y)| (C::from_x_y(*x, *y), Cell::Active))
                 .collect(),
         }
     }
@@ -131,7 +125,7 @@ impl<C: Coordinate> Automaton<C> {
     }
 
     /// Drive the automaton forward one step
-    fn step(&mut self) {
+    pub fn step(&mut self) {
         let mut new = self.duplicate_grid();
         for (coord, ref mut v) in new.iter_mut() {
             **v = self.update_cell(*coord);
@@ -140,7 +134,7 @@ impl<C: Coordinate> Automaton<C> {
     }
 
     /// Count the number of active cells
-    fn count_active(&self) -> usize {
+    pub fn count_active(&self) -> usize {
         self.cells.values().filter(|v| **v == Cell::Active).count()
     }
 }
@@ -177,22 +171,51 @@ const THREE_CUBE_COORDS: &[(isize, isize, isize)] = &[
     (1, 1, 1),
 ];
 
-pub fn part1() {
-    let mut automaton = Automaton::<(_, _, _)>::load_from_data();
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: usize = 17;
+
+    /// The coordinates of the active cells in the initial 2D slice
+    type Input = Vec<(isize, isize)>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        let grid = Grid::parse(lines, |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })?;
+        Ok(grid
+            .iter()
+            .filter(|(_, active)| **active)
+            .map(|((x, y), _)| (x as isize, y as isize))
+            .collect())
+    }
+
+    fn part1(active: &Self::Input) -> Answer {
+        let mut automaton = Automaton::<(_, _, _)>::from_slice(active);
 
-    for _ in 0..6 {
-        automaton.step();
+        for _ in 0..6 {
+            automaton.step();
+        }
+
+        automaton.count_active().into()
     }
 
-    println!("Day 17, Part 1: {}", automaton.count_active());
-}
+    fn part2(active: &Self::Input) -> Answer {
+        let mut automaton = Automaton::<(_, _, _, _)>::from_slice(active);
 
-pub fn part2() {
-    let mut automaton = Automaton::<(_, _, _, _)>::load_from_data();
+        for _ in 0..6 {
+            automaton.step();
+        }
 
-    for _ in 0..6 {
-        automaton.step();
+        automaton.count_active().into()
     }
+}
 
-    println!("Day 17, Part 2: {}", automaton.count_active());
+#[test]
+fn test_example() {
+    let active = Day17::parse(crate::input::from_str(".#.\n..#\n###")).unwrap();
+    assert_eq!(Day17::part1(&active), 112);
+    assert_eq!(Day17::part2(&active), 848);
 }
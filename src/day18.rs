@@ -1,8 +1,6 @@
-fn load_data() -> Vec<Vec<u8>> {
-    crate::util::data_lines(18)
-        .map(|line| line.bytes().filter(|b| !b.is_ascii_whitespace()).collect())
-        .collect()
-}
+use crate::error::Result;
+use crate::input::{Line, Lines};
+use crate::solution::{Answer, Solution};
 
 /// A stack-based APU to be driven by the shunting yard algorithm
 struct Output(Vec<usize>);
@@ -29,7 +27,11 @@ impl Output {
     }
 }
 
-fn shunting_yard(input: impl Iterator<Item = u8>, precedence: fn(u8) -> u8) -> usize {
+/// Evaluate an expression of single digits, `+`, `*` and brackets, with operators binding as tightly
+/// as `precedence` says (higher binds tighter, equal binds left to right).
+///
+/// The tokens must form a well-formed expression, otherwise this panics.
+pub fn shunting_yard(input: impl Iterator<Item = u8>, precedence: fn(u8) -> u8) -> usize {
     let mut output = Output(Vec::new());
     let mut siding = Vec::new();
     for token in input {
@@ -74,36 +76,113 @@ fn shunting_yard(input: impl Iterator<Item = u8>, precedence: fn(u8) -> u8) -> u
     output.finish()
 }
 
-pub fn part1() {
-    fn part_1_precedence(_op: u8) -> u8 {
-        // All operations are left-associative and of equal priority
-        0
+/// Read the tokens of an expression, checking it's well-formed enough for `shunting_yard`: single
+/// digits alternating with operators, and balanced brackets
+fn parse_expression(line: &Line) -> Result<Vec<u8>> {
+    let mut tokens = Vec::with_capacity(line.len());
+    // Whether the next token should be a digit or `(` (rather than an operator or `)`)
+    let mut expect_operand = true;
+    // Where each unclosed `(` is
+    let mut open = Vec::new();
+    for (idx, b) in line.bytes().enumerate() {
+        match b {
+            b if b.is_ascii_whitespace() => continue,
+            b'0'..=b'9' | b'(' if !expect_operand => {
+                return Err(line.error_at(idx, format!("expected an operator, found '{}'", b as char)))
+            }
+            b'+' | b'*' | b')' if expect_operand => {
+                return Err(line.error_at(idx, format!("expected a digit, found '{}'", b as char)))
+            }
+            b'0'..=b'9' => expect_operand = false,
+            b'(' => open.push(idx),
+            b')' => {
+                if open.pop().is_none() {
+                    return Err(line.error_at(idx, "unmatched ')'"));
+                }
+            }
+            b'+' | b'*' => expect_operand = true,
+            _ => return Err(line.error_at(idx, format!("unexpected token '{}'", b as char))),
+        }
+        tokens.push(b);
     }
-
-    println!(
-        "Day 18, Part 1: {}",
-        load_data()
-            .into_iter()
-            .map(|problem| shunting_yard(problem.into_iter(), part_1_precedence))
-            .sum::<usize>()
-    );
+    if expect_operand {
+        return Err(line.error_at(line.len(), "expected a digit"));
+    }
+    if let Some(idx) = open.pop() {
+        return Err(line.error_at(idx, "unmatched '('"));
+    }
+    Ok(tokens)
 }
 
-pub fn part2() {
-    fn part_2_precedence(op: u8) -> u8 {
-        match op {
-            // All operations are left-associative but `+` binds harder than `*`
-            b'+' => 2,
-            b'*' => 1,
-            _ => unreachable!(),
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: usize = 18;
+
+    type Input = Vec<Vec<u8>>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(parse_expression)
+    }
+
+    fn part1(problems: &Self::Input) -> Answer {
+        fn part_1_precedence(_op: u8) -> u8 {
+            // All operations are left-associative and of equal priority
+            0
         }
+
+        problems
+            .iter()
+            .map(|problem| shunting_yard(problem.iter().copied(), part_1_precedence))
+            .sum::<usize>()
+            .into()
     }
 
-    println!(
-        "Day 18, Part 2: {}",
-        load_data()
-            .into_iter()
-            .map(|problem| shunting_yard(problem.into_iter(), part_2_precedence))
+    fn part2(problems: &Self::Input) -> Answer {
+        fn part_2_precedence(op: u8) -> u8 {
+            match op {
+                // All operations are left-associative but `+` binds harder than `*`
+                b'+' => 2,
+                b'*' => 1,
+                _ => unreachable!(),
+            }
+        }
+
+        problems
+            .iter()
+            .map(|problem| shunting_yard(problem.iter().copied(), part_2_precedence))
             .sum::<usize>()
-    );
+            .into()
+    }
+}
+
+#[test]
+fn test_examples() {
+    for (problem, part1, part2) in [
+        ("1 + 2 * 3 + 4 * 5 + 6", 71, 231),
+        ("1 + (2 * 3) + (4 * (5 + 6))", 51, 51),
+        ("2 * 3 + (4 * 5)", 26, 46),
+        ("5 + (8 * 3 + 9 + 3 * 4 * 3)", 437, 1445),
+        ("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", 12240, 669060),
+        ("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2", 13632, 23340),
+    ] {
+        let problems = Day18::parse(crate::input::from_str(problem)).unwrap();
+        assert_eq!(Day18::part1(&problems), part1, "{}", problem);
+        assert_eq!(Day18::part2(&problems), part2, "{}", problem);
+    }
+}
+
+#[test]
+fn test_malformed() {
+    for (problem, error) in [
+        ("(1 + 2", "<input>:1:1: unmatched '('"),
+        ("1 + 2)", "<input>:1:6: unmatched ')'"),
+        ("1 +", "<input>:1:4: expected a digit"),
+        ("12 + 3", "<input>:1:2: expected an operator, found '2'"),
+        ("1 + * 2", "<input>:1:5: expected a digit, found '*'"),
+        ("1 - 2", "<input>:1:3: unexpected token '-'"),
+    ] {
+        let err = Day18::parse(crate::input::from_str(problem)).err().unwrap();
+        assert_eq!(err.to_string(), error);
+    }
 }
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+
+use crate::error::{Error, Result};
+use crate::input::{Line, Lines};
+use crate::solution::{Answer, Solution};
+use crate::util::parse::Field;
+
+/// One rule of a `Grammar`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rule {
+    /// Matches exactly this text
+    Literal(String),
+    /// Matches any one of the sequences of rules
+    Alternatives(Vec<Vec<usize>>),
+}
+
+impl Rule {
+    /// Parse the body of a rule, e.g. `"a"`, `4 1 5` or `2 3 | 3 2`
+    fn parse(body: Field) -> Result<Self> {
+        if body.starts_with('"') {
+            let literal = body.strip_prefix("\"")?.strip_suffix("\"")?;
+            return Ok(Rule::Literal(literal.to_string()));
+        }
+        body.split(" | ")
+            .map(|sequence| {
+                sequence
+                    .split(" ")
+                    .map(|id| id.parse())
+                    .collect::<Result<Vec<_>>>()
+            })
+            .collect::<Result<_>>()
+            .map(Rule::Alternatives)
+    }
+
+    /// The ids of the rules this rule refers to
+    fn references(&self) -> impl Iterator<Item = usize> + '_ {
+        let sequences = match self {
+            Rule::Literal(_) => &[][..],
+            Rule::Alternatives(sequences) => &sequences[..],
+        };
+        sequences.iter().flatten().copied()
+    }
+}
+
+/// A set of numbered rules describing which messages are valid, like
+///
+/// ```text
+/// 0: 1 2
+/// 1: "a"
+/// 2: 1 3 | 3 1
+/// 3: "b"
+/// ```
+///
+/// Rules may refer to themselves (e.g. `8: 42 | 42 8`), as long as they always match some text
+/// before doing so, since every possible way of matching is explored.
+#[derive(Debug, Clone, Default)]
+pub struct Grammar {
+    rules: HashMap<usize, Rule>,
+}
+
+impl Grammar {
+    /// Read rules like `2: 1 3 | 3 1`, one per line, up to a blank line or the end of the input.
+    ///
+    /// Every rule a rule refers to must be defined.
+    pub fn parse(lines: &mut Lines) -> Result<Self> {
+        let mut rules = Vec::new();
+        for line in lines {
+            let line = line?;
+            if line.is_empty() {
+                break;
+            }
+            let (id, rule) = parse_rule(&line)?;
+            rules.push((line, id, rule));
+        }
+
+        let mut grammar = Grammar::default();
+        for (_, id, rule) in &rules {
+            grammar.insert(*id, rule.clone());
+        }
+        for (line, id, rule) in &rules {
+            if let Some(missing) = rule.references().find(|id| !grammar.rules.contains_key(id)) {
+                return Err(line.error(format!("rule {} refers to missing rule {}", id, missing)));
+            }
+        }
+        Ok(grammar)
+    }
+
+    /// Add a rule, replacing any existing rule with the same id
+    pub fn insert(&mut self, id: usize, rule: Rule) {
+        self.rules.insert(id, rule);
+    }
+
+    /// Whether the whole of `message` matches the given rule.
+    ///
+    /// Panics if a rule needed to decide refers to a rule that doesn't exist.
+    pub fn matches(&self, rule: usize, message: &str) -> bool {
+        self.match_rule(rule, message, 0).contains(&message.len())
+    }
+
+    /// Every position a match of `rule` starting at `start` could end at
+    fn match_rule(&self, rule: usize, message: &str, start: usize) -> Vec<usize> {
+        match &self.rules[&rule] {
+            Rule::Literal(literal) => {
+                if message[start..].starts_with(literal.as_str()) {
+                    vec![start + literal.len()]
+                } else {
+                    vec![]
+                }
+            }
+            Rule::Alternatives(sequences) => sequences
+                .iter()
+                .flat_map(|sequence| self.match_sequence(sequence, message, start))
+                .collect(),
+        }
+    }
+
+    /// Every position a match of the rules one after the other starting at `start` could end at
+    fn match_sequence(&self, sequence: &[usize], message: &str, start: usize) -> Vec<usize> {
+        sequence.iter().fold(vec![start], |ends, rule| {
+            ends.into_iter()
+                .flat_map(|end| self.match_rule(*rule, message, end))
+                .collect()
+        })
+    }
+}
+
+impl std::str::FromStr for Grammar {
+    type Err = Error;
+
+    /// Parse rules written one per line, as in the puzzle input
+    fn from_str(text: &str) -> Result<Self> {
+        let mut lines = crate::input::from_str(text);
+        let grammar = Grammar::parse(&mut lines)?;
+        match lines.next() {
+            Some(line) => Err(line?.error("unexpected line after the rules")),
+            None => Ok(grammar),
+        }
+    }
+}
+
+/// The grammar rules and the messages to check against them
+pub struct Notes {
+    grammar: Grammar,
+    messages: Vec<String>,
+}
+
+/// Parse a line like `2: 1 3 | 3 1`
+fn parse_rule(line: &Line) -> Result<(usize, Rule)> {
+    let (id, body) = Field::new(line).split_once(": ")?;
+    Ok((id.parse()?, Rule::parse(body)?))
+}
+
+/// Count the messages fully matched by rule 0
+fn count_matches(grammar: &Grammar, messages: &[String]) -> usize {
+    messages
+        .iter()
+        .filter(|message| grammar.matches(0, message))
+        .count()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: usize = 19;
+
+    type Input = Notes;
+
+    fn parse(mut lines: Lines) -> Result<Self::Input> {
+        let grammar = Grammar::parse(&mut lines)?;
+        let messages = lines.parse_each(|line| Ok(line.to_string()))?;
+        Ok(Notes { grammar, messages })
+    }
+
+    fn part1(notes: &Self::Input) -> Answer {
+        count_matches(&notes.grammar, &notes.messages).into()
+    }
+
+    fn part2(notes: &Self::Input) -> Answer {
+        let mut grammar = notes.grammar.clone();
+        // 8: 42 | 42 8
+        grammar.insert(8, Rule::Alternatives(vec![vec![42], vec![42, 8]]));
+        // 11: 42 31 | 42 11 31
+        grammar.insert(11, Rule::Alternatives(vec![vec![42, 31], vec![42, 11, 31]]));
+        count_matches(&grammar, &notes.messages).into()
+    }
+}
+
+#[test]
+fn test_example_part1() {
+    let notes = Day19::parse(crate::input::from_str(
+        r#"0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: "a"
+5: "b"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb"#,
+    ))
+    .unwrap();
+    assert_eq!(Day19::part1(&notes), 2);
+}
+
+#[test]
+fn test_example_part2() {
+    let notes = Day19::parse(crate::input::from_str(
+        r#"42: 9 14 | 10 1
+9: 14 27 | 1 26
+10: 23 14 | 28 1
+1: "a"
+11: 42 31
+5: 1 14 | 15 1
+19: 14 1 | 14 14
+12: 24 14 | 19 1
+16: 15 1 | 14 14
+31: 14 17 | 1 13
+6: 14 14 | 1 14
+2: 1 24 | 14 4
+0: 8 11
+13: 14 3 | 1 12
+15: 1 | 14
+17: 14 2 | 1 7
+23: 25 1 | 22 14
+28: 16 1
+4: 1 1
+20: 14 14 | 1 15
+3: 5 14 | 16 1
+27: 1 6 | 14 18
+14: "b"
+21: 14 1 | 1 14
+25: 1 1 | 1 14
+22: 14 14
+8: 42
+26: 14 22 | 1 20
+18: 15 15
+7: 14 5 | 1 21
+24: 14 1
+
+abbbbbabbbaaaababbaabbbbabababbbabbbbbbabaaaa
+bbabbbbaabaabba
+babbbbaabbbbbabbbbbbaabaaabaaa
+aaabbbbbbaaaabaababaabababbabaaabbababababaaa
+bbbbbbbaaaabbbbaaabbabaaa
+bbbababbbbaaaaaaaabbababaaababaabab
+ababaaaaaabaaab
+ababaaaaabbbaba
+baabbaaaabbaaaababbaababb
+abbbbabbbbaaaababbbbbbaaaababb
+aaaaabbaabaaaaababaa
+aaaabbaaaabbaaa
+aaaabbaabbaaaaaaabbbabbbaaabbaabaaa
+babaaabbbaaabaababbaabababaaab
+aabbbbbaabbbaaaaaabbbbbababaaaaabbaaabba"#,
+    ))
+    .unwrap();
+    assert_eq!(Day19::part1(&notes), 3);
+    assert_eq!(Day19::part2(&notes), 12);
+}
+
+#[test]
+fn test_missing_rule() {
+    let err = Day19::parse(crate::input::from_str("0: 1 2\n1: \"a\"\n\na")).err().unwrap();
+    assert_eq!(err.to_string(), "<input>:1: rule 0 refers to missing rule 2");
+}
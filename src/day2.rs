@@ -1,6 +1,12 @@
+// The `recap::Recap` derive generates code that trips these lints
+#![allow(clippy::needless_question_mark, non_local_definitions)]
+
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
 #[derive(recap::Recap, serde::Deserialize, Debug)]
 #[recap(regex=r#"^(?P<low>\d+)-(?P<high>\d+) (?P<letter>[a-z]): (?P<data>[a-z]+)$"#)]
-struct PasswordEntry {
+pub struct PasswordEntry {
     letter: char,
     low: usize,
     high: usize,
@@ -20,20 +26,35 @@ impl PasswordEntry {
     }
 }
 
-fn load_data() -> Vec<PasswordEntry> {
-    crate::util::data_lines(2).map(|s| s.parse::<PasswordEntry>().unwrap()).collect()
-}
+pub struct Day2;
 
-pub fn part1() {
-    let data = load_data();
-    let valid = data.iter().filter(|p| p.validate_old());
-    println!("Day 2, Part 1: {}", valid.count());
-}
+impl Solution for Day2 {
+    const DAY: usize = 2;
+
+    type Input = Vec<PasswordEntry>;
+
+    fn parse(lines: Lines) -> crate::error::Result<Self::Input> {
+        lines.parse_each(|line| {
+            let entry = line.parse_as::<PasswordEntry>()?;
+            if entry.low == 0 || entry.high > entry.data.chars().count() {
+                return Err(line.error(format!(
+                    "positions {}-{} aren't all within the password",
+                    entry.low, entry.high
+                )));
+            }
+            Ok(entry)
+        })
+    }
 
-pub fn part2() {
-    let data = load_data();
-    let valid = data.iter().filter(|p| p.validate_new());
-    println!("Day 2, Part 2: {}", valid.count());
+    fn part1(data: &Self::Input) -> Answer {
+        let valid = data.iter().filter(|p| p.validate_old());
+        valid.count().into()
+    }
+
+    fn part2(data: &Self::Input) -> Answer {
+        let valid = data.iter().filter(|p| p.validate_new());
+        valid.count().into()
+    }
 }
 
 #[test]
@@ -56,3 +77,13 @@ fn test_2_9_c_ccccccccc() {
     assert!(!p.validate_new());
     assert!(p.validate_old());
 }
+
+#[test]
+fn test_example() {
+    let data = Day2::parse(crate::input::from_str(
+        "1-3 a: abcde\n1-3 b: cdefg\n2-9 c: ccccccccc",
+    ))
+    .unwrap();
+    assert_eq!(Day2::part1(&data), 2);
+    assert_eq!(Day2::part2(&data), 1);
+}
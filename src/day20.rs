@@ -0,0 +1,329 @@
+use std::collections::HashSet;
+
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::grid::Grid;
+use crate::util::parse::Field;
+
+/// One piece of the jigsaw: a square of image data with an id
+pub struct Tile {
+    id: usize,
+    image: Grid<bool>,
+}
+
+/// The sea monster, as it appears in the puzzle
+const SEA_MONSTER: &[&str] = &[
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+/// Whether `right` can sit immediately to the right of `left`
+fn fits_horizontally(left: &Grid<bool>, right: &Grid<bool>) -> bool {
+    left.column(left.width() - 1).eq(right.column(0))
+}
+
+/// Whether `below` can sit immediately below `above`
+fn fits_vertically(above: &Grid<bool>, below: &Grid<bool>) -> bool {
+    above.row(above.height() - 1) == below.row(0)
+}
+
+/// Fill in the square from `placed.len()` onwards, row by row, with (tile, orientation) pairs that
+/// line up with their neighbours, backtracking as necessary
+fn place(
+    side: usize,
+    orientations: &[Vec<Grid<bool>>],
+    placed: &mut Vec<(usize, usize)>,
+    used: &mut [bool],
+) -> bool {
+    let position = placed.len();
+    if position == orientations.len() {
+        return true;
+    }
+    let (x, y) = (position % side, position / side);
+    for tile in 0..orientations.len() {
+        if used[tile] {
+            continue;
+        }
+        for (orientation, image) in orientations[tile].iter().enumerate() {
+            let fits_left = x == 0 || {
+                let (tile, orientation) = placed[position - 1];
+                fits_horizontally(&orientations[tile][orientation], image)
+            };
+            let fits_above = y == 0 || {
+                let (tile, orientation) = placed[position - side];
+                fits_vertically(&orientations[tile][orientation], image)
+            };
+            if fits_left && fits_above {
+                used[tile] = true;
+                placed.push((tile, orientation));
+                if place(side, orientations, placed, used) {
+                    return true;
+                }
+                placed.pop();
+                used[tile] = false;
+            }
+        }
+    }
+    false
+}
+
+/// Arrange the tiles into a square, each rotated and flipped to line up with its neighbours,
+/// returning the id and oriented image of each tile
+fn assemble(tiles: &[Tile]) -> Grid<(usize, Grid<bool>)> {
+    let side = (1..=tiles.len())
+        .find(|side| side * side >= tiles.len())
+        .unwrap_or(0);
+    let orientations = tiles
+        .iter()
+        .map(|tile| tile.image.orientations())
+        .collect::<Vec<_>>();
+    let mut placed = Vec::with_capacity(tiles.len());
+    let mut used = vec![false; tiles.len()];
+    assert!(
+        place(side, &orientations, &mut placed, &mut used),
+        "The tiles don't fit together"
+    );
+    let mut placed = placed.into_iter();
+    Grid::from_fn(side, side, |_| {
+        let (tile, orientation) = placed.next().expect("Every position has a tile");
+        (tiles[tile].id, orientations[tile][orientation].clone())
+    })
+}
+
+/// Join the assembled tiles into a single image, without their borders
+fn stitch(arrangement: &Grid<(usize, Grid<bool>)>) -> Grid<bool> {
+    let (_, first) = &arrangement[(0, 0)];
+    let inner = first.width() - 2;
+    Grid::from_fn(
+        arrangement.width() * inner,
+        arrangement.height() * inner,
+        |(x, y)| {
+            let (_, image) = &arrangement[(x / inner, y / inner)];
+            image[(x % inner + 1, y % inner + 1)]
+        },
+    )
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: usize = 20;
+
+    type Input = Vec<Tile>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        let mut records = crate::util::records(lines);
+        let mut size = None;
+        let tiles = records
+            .by_ref()
+            .map(|record| {
+                let mut record = record?.into_iter();
+                let header = record.next().expect("Records aren't empty");
+                let id = Field::new(&header)
+                    .strip_prefix("Tile ")?
+                    .strip_suffix(":")?
+                    .parse()?;
+                let image = Grid::parse(record.map(Ok), |c| match c {
+                    '#' => Some(true),
+                    '.' => Some(false),
+                    _ => None,
+                })?;
+                if image.width() != image.height() || image.width() < 3 {
+                    return Err(header.error("tile isn't a square of at least 3x3"));
+                }
+                // Every tile must match the first, so their edges can line up
+                let size = *size.get_or_insert(image.width());
+                if image.width() != size {
+                    return Err(header.error(format!(
+                        "expected a {}x{} tile like the others, found {}x{}",
+                        size,
+                        size,
+                        image.width(),
+                        image.height()
+                    )));
+                }
+                Ok(Tile { id, image })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if tiles.is_empty() {
+            return Err(records.error("expected at least one tile"));
+        }
+        let side = (1..).find(|side| side * side >= tiles.len()).unwrap_or(0);
+        if side * side != tiles.len() {
+            let message = format!("{} tiles can't be arranged in a square", tiles.len());
+            return Err(records.error(message));
+        }
+        Ok(tiles)
+    }
+
+    fn part1(tiles: &Self::Input) -> Answer {
+        let arrangement = assemble(tiles);
+        let (right, bottom) = (arrangement.width() - 1, arrangement.height() - 1);
+        [(0, 0), (right, 0), (0, bottom), (right, bottom)]
+            .iter()
+            .map(|corner| arrangement[*corner].0)
+            .product::<usize>()
+            .into()
+    }
+
+    fn part2(tiles: &Self::Input) -> Answer {
+        let image = stitch(&assemble(tiles));
+        let monster = SEA_MONSTER
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.char_indices()
+                    .filter(|(_, c)| *c == '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect::<Vec<_>>();
+        let (monster_width, monster_height) = (SEA_MONSTER[0].len(), SEA_MONSTER.len());
+
+        let rough_water = image.iter().filter(|(_, cell)| **cell).count();
+        for image in image.orientations() {
+            let mut monster_cells = HashSet::new();
+            for y in 0..=image.height().saturating_sub(monster_height) {
+                for x in 0..=image.width().saturating_sub(monster_width) {
+                    let cells = monster.iter().map(|(dx, dy)| (x + dx, y + dy));
+                    if cells.clone().all(|cell| image.get(cell) == Some(&true)) {
+                        monster_cells.extend(cells);
+                    }
+                }
+            }
+            // Only one orientation has any sea monsters in it
+            if !monster_cells.is_empty() {
+                return (rough_water - monster_cells.len()).into();
+            }
+        }
+        rough_water.into()
+    }
+}
+
+#[test]
+fn test_example() {
+    let tiles = Day20::parse(crate::input::from_str(
+        "Tile 2311:
+..##.#..#.
+##..#.....
+#...##..#.
+####.#...#
+##.##.###.
+##...#.###
+.#.#.#..##
+..#....#..
+###...#.#.
+..###..###
+
+Tile 1951:
+#.##...##.
+#.####...#
+.....#..##
+#...######
+.##.#....#
+.###.#####
+###.##.##.
+.###....#.
+..#.#..#.#
+#...##.#..
+
+Tile 1171:
+####...##.
+#..##.#..#
+##.#..#.#.
+.###.####.
+..###.####
+.##....##.
+.#...####.
+#.##.####.
+####..#...
+.....##...
+
+Tile 1427:
+###.##.#..
+.#..#.##..
+.#.##.#..#
+#.#.#.##.#
+....#...##
+...##..##.
+...#.#####
+.#.####.#.
+..#..###.#
+..##.#..#.
+
+Tile 1489:
+##.#.#....
+..##...#..
+.##..##...
+..#...#...
+#####...#.
+#..#.#.#.#
+...#.#.#..
+##.#...##.
+..##.##.##
+###.##.#..
+
+Tile 2473:
+#....####.
+#..#.##...
+#.##..#...
+######.#.#
+.#...#.#.#
+.#########
+.###.#..#.
+########.#
+##...##.#.
+..###.#.#.
+
+Tile 2971:
+..#.#....#
+#...###...
+#.#.###...
+##.##..#..
+.#####..##
+.#..####.#
+#..#.#..#.
+..####.###
+..#.#.###.
+...#.#.#.#
+
+Tile 2729:
+...#.#.#.#
+####.#....
+..#.#.....
+....#..#.#
+.##..##.#.
+.#.####...
+####.#.#..
+##.####...
+##..#.##..
+#.##...##.
+
+Tile 3079:
+#.#.#####.
+.#..######
+..#.......
+######....
+####.#..#.
+.#...#.##.
+#.#####.##
+..#.###...
+..#.......
+..#.###...",
+    ))
+    .unwrap();
+    assert_eq!(Day20::part1(&tiles), 20899048083289);
+    assert_eq!(Day20::part2(&tiles), 273);
+}
+
+#[test]
+fn test_invalid_tiles() {
+    let err = Day20::parse(crate::input::from_str("")).err().unwrap();
+    assert_eq!(err.to_string(), "<input>:1: expected at least one tile");
+    let err = Day20::parse(crate::input::from_str("Tile 1:\n#..\n.#.\n..#\n\nTile 2:\n#...\n.#..\n..#.\n...#"))
+        .err()
+        .unwrap();
+    assert_eq!(err.to_string(), "<input>:6: expected a 3x3 tile like the others, found 4x4");
+}
@@ -0,0 +1,92 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::error::Result;
+use crate::input::{Line, Lines};
+use crate::solution::{Answer, Solution};
+use crate::util::assign_unique;
+use crate::util::parse::Field;
+
+/// One food's ingredients, and some (but not necessarily all) of the allergens it contains
+pub struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+
+impl Food {
+    /// Parse a line like `mxmxvkd kfcds sqjhc nhms (contains dairy, fish)`
+    fn parse(line: &Line) -> Result<Self> {
+        let (ingredients, allergens) = Field::new(line).split_once(" (contains ")?;
+        let allergens = allergens.strip_suffix(")")?;
+        Ok(Food {
+            ingredients: ingredients.split(" ").map(|i| i.to_string()).collect(),
+            allergens: allergens.split(", ").map(|a| a.to_string()).collect(),
+        })
+    }
+}
+
+/// The ingredients that could contain each allergen, i.e. those in every food listing it
+fn candidates(foods: &[Food]) -> HashMap<&str, HashSet<&str>> {
+    let mut candidates = HashMap::<&str, HashSet<&str>>::new();
+    for food in foods {
+        let ingredients = food.ingredients.iter().map(String::as_str).collect::<HashSet<_>>();
+        for allergen in &food.allergens {
+            candidates
+                .entry(allergen)
+                .and_modify(|candidates| candidates.retain(|i| ingredients.contains(i)))
+                .or_insert_with(|| ingredients.clone());
+        }
+    }
+    candidates
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: usize = 21;
+
+    type Input = Vec<Food>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(Food::parse)
+    }
+
+    fn part1(foods: &Self::Input) -> Answer {
+        let unsafe_ingredients = candidates(foods)
+            .into_values()
+            .flatten()
+            .collect::<HashSet<_>>();
+        foods
+            .iter()
+            .flat_map(|food| &food.ingredients)
+            .filter(|ingredient| !unsafe_ingredients.contains(ingredient.as_str()))
+            .count()
+            .into()
+    }
+
+    fn part2(foods: &Self::Input) -> Answer {
+        let allergen_to_ingredient = assign_unique(candidates(foods))
+            .expect("The allergens can't be told apart")
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+        // The canonical list is sorted by allergen
+        allergen_to_ingredient
+            .values()
+            .copied()
+            .collect::<Vec<_>>()
+            .join(",")
+            .into()
+    }
+}
+
+#[test]
+fn test_example() {
+    let foods = Day21::parse(crate::input::from_str(
+        "mxmxvkd kfcds sqjhc nhms (contains dairy, fish)
+trh fvjkl sbzzf mxmxvkd (contains dairy)
+sqjhc fvjkl (contains soy)
+sqjhc mxmxvkd sbzzf (contains fish)",
+    ))
+    .unwrap();
+    assert_eq!(Day21::part1(&foods), 5);
+    assert_eq!(Day21::part2(&foods), "mxmxvkd,sqjhc,fvjkl");
+}
@@ -0,0 +1,195 @@
+use std::collections::{HashSet, VecDeque};
+
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+/// A player's cards, from the top of the deck to the bottom
+pub type Deck = VecDeque<usize>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+impl Player {
+    fn index(self) -> usize {
+        match self {
+            Player::One => 0,
+            Player::Two => 1,
+        }
+    }
+}
+
+/// A game of Combat, which can be played a round at a time with `Game::step`
+#[derive(Debug, Clone)]
+pub struct Game {
+    decks: [Deck; 2],
+    /// Whether to play Recursive Combat rather than the simple game
+    recursive: bool,
+    /// Every pair of decks the players have started a round with (only tracked when recursive)
+    seen: HashSet<[Deck; 2]>,
+}
+
+/// The outcome of playing one round
+pub enum PostStep {
+    /// The game carries on
+    Game(Game),
+    /// The game is over, and here's who won
+    Won(Player, Game),
+}
+
+impl Game {
+    pub fn new(decks: [Deck; 2], recursive: bool) -> Self {
+        Game {
+            decks,
+            recursive,
+            seen: HashSet::new(),
+        }
+    }
+
+    pub fn deck(&self, player: Player) -> &Deck {
+        &self.decks[player.index()]
+    }
+
+    /// The sum of each card in the player's deck multiplied by its position from the bottom
+    pub fn score(&self, player: Player) -> usize {
+        self.deck(player)
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(idx, card)| (idx + 1) * card)
+            .sum()
+    }
+
+    /// Play one round, or declare the winner if there's nothing left to play
+    pub fn step(mut self) -> PostStep {
+        if self.decks[1].is_empty() {
+            return PostStep::Won(Player::One, self);
+        }
+        if self.decks[0].is_empty() {
+            return PostStep::Won(Player::Two, self);
+        }
+        // Player one wins outright if these decks have been played before, to stop infinite games
+        if self.recursive && !self.seen.insert(self.decks.clone()) {
+            return PostStep::Won(Player::One, self);
+        }
+
+        let cards = [
+            self.decks[0].pop_front().expect("Both decks have cards"),
+            self.decks[1].pop_front().expect("Both decks have cards"),
+        ];
+        let winner = if self.recursive
+            && cards[0] <= self.decks[0].len()
+            && cards[1] <= self.decks[1].len()
+        {
+            let sub_decks = [
+                self.decks[0].iter().take(cards[0]).copied().collect(),
+                self.decks[1].iter().take(cards[1]).copied().collect(),
+            ];
+            Game::new(sub_decks, true).play().0
+        } else if cards[0] > cards[1] {
+            Player::One
+        } else {
+            Player::Two
+        };
+
+        // The winner's card goes underneath first
+        let deck = &mut self.decks[winner.index()];
+        deck.push_back(cards[winner.index()]);
+        deck.push_back(cards[1 - winner.index()]);
+        PostStep::Game(self)
+    }
+
+    /// Play rounds until someone wins
+    pub fn play(mut self) -> (Player, Game) {
+        loop {
+            self = match self.step() {
+                PostStep::Game(game) => game,
+                PostStep::Won(winner, game) => return (winner, game),
+            };
+        }
+    }
+}
+
+/// Play a whole game, returning the winner's score
+fn winning_score(decks: &[Deck; 2], recursive: bool) -> usize {
+    let (winner, game) = Game::new(decks.clone(), recursive).play();
+    game.score(winner)
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: usize = 22;
+
+    type Input = [Deck; 2];
+
+    fn parse(mut lines: Lines) -> Result<Self::Input> {
+        let mut decks = [Deck::new(), Deck::new()];
+        for (idx, deck) in decks.iter_mut().enumerate() {
+            let header = format!("Player {}:", idx + 1);
+            let line = lines.next_line()?;
+            if *line != header {
+                return Err(line.error(format!("expected '{}'", header)));
+            }
+            for line in &mut lines {
+                let line = line?;
+                if line.is_empty() {
+                    break;
+                }
+                deck.push_back(line.parse_as()?);
+            }
+        }
+        if let Some(line) = lines.next() {
+            return Err(line?.error("unexpected line after both decks"));
+        }
+        Ok(decks)
+    }
+
+    fn part1(decks: &Self::Input) -> Answer {
+        winning_score(decks, false).into()
+    }
+
+    fn part2(decks: &Self::Input) -> Answer {
+        winning_score(decks, true).into()
+    }
+}
+
+#[test]
+fn test_example() {
+    let decks = Day22::parse(crate::input::from_str(
+        "Player 1:
+9
+2
+6
+3
+1
+
+Player 2:
+5
+8
+4
+7
+10",
+    ))
+    .unwrap();
+    assert_eq!(Day22::part1(&decks), 306);
+    assert_eq!(Day22::part2(&decks), 291);
+
+    // Player 1's 9 beats player 2's 5 in the first round
+    let game = match Game::new(decks.clone(), false).step() {
+        PostStep::Game(game) => game,
+        PostStep::Won(..) => panic!("The game ended after one round"),
+    };
+    assert_eq!(game.deck(Player::One), &[2, 6, 3, 1, 9, 5]);
+    assert_eq!(game.deck(Player::Two), &[8, 4, 7, 10]);
+}
+
+#[test]
+fn test_infinite_game() {
+    let decks = Day22::parse(crate::input::from_str("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14")).unwrap();
+    let (winner, _) = Game::new(decks, true).play();
+    assert_eq!(winner, Player::One);
+}
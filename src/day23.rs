@@ -0,0 +1,150 @@
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+/// The fewest cups the game can be played with
+const MIN_CUPS: usize = 5;
+
+/// A circle of cups labelled `1..=len`, stored as the label of the cup clockwise of each cup.
+///
+/// Moving a run of cups elsewhere in the circle only changes three entries, which makes each move
+/// constant time, unlike shuffling the cups around in a `VecDeque`.
+struct Cups {
+    /// `next[label]` is the cup clockwise of `label` (`next[0]` is unused)
+    next: Vec<u32>,
+    current: u32,
+}
+
+impl Cups {
+    /// Arrange the cups in the given order, followed by the rest of the cups up to `len` in order
+    fn new(start: &[u32], len: usize) -> Self {
+        assert!(len >= MIN_CUPS, "The game needs at least {} cups", MIN_CUPS);
+        let order = start
+            .iter()
+            .copied()
+            .chain(start.len() as u32 + 1..=len as u32)
+            .collect::<Vec<_>>();
+        let mut next = vec![0; len + 1];
+        for (cup, after) in order.iter().zip(order.iter().cycle().skip(1)) {
+            next[*cup as usize] = *after;
+        }
+        Cups {
+            next,
+            current: order[0],
+        }
+    }
+
+    fn after(&self, cup: u32) -> u32 {
+        self.next[cup as usize]
+    }
+
+    /// Pick up the three cups after the current cup, put them down after the destination cup,
+    /// then move on to the next cup
+    fn make_move(&mut self) {
+        let len = self.next.len() as u32 - 1;
+        let first = self.after(self.current);
+        let second = self.after(first);
+        let third = self.after(second);
+
+        // There are at least 5 cups, so some cup other than these 4 is the destination
+        let mut destination = self.current;
+        loop {
+            destination = if destination == 1 {
+                len
+            } else {
+                destination - 1
+            };
+            if destination != first && destination != second && destination != third {
+                break;
+            }
+        }
+
+        self.next[self.current as usize] = self.after(third);
+        self.next[third as usize] = self.after(destination);
+        self.next[destination as usize] = first;
+        self.current = self.after(self.current);
+    }
+
+    /// The labels clockwise of cup 1, not including cup 1 itself
+    fn after_one(&self) -> impl Iterator<Item = u32> + '_ {
+        std::iter::successors(Some(self.after(1)), move |cup| Some(self.after(*cup)))
+            .take_while(|cup| *cup != 1)
+    }
+}
+
+/// Play the game from the starting arrangement, returning the final circle
+fn play(start: &[u32], len: usize, moves: usize) -> Cups {
+    let mut cups = Cups::new(start, len);
+    for _ in 0..moves {
+        cups.make_move();
+    }
+    cups
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: usize = 23;
+
+    type Input = Vec<u32>;
+
+    fn parse(mut lines: Lines) -> Result<Self::Input> {
+        let line = lines.next_line()?;
+        let mut cups = Vec::with_capacity(line.len());
+        for (idx, c) in line.char_indices() {
+            match c.to_digit(10) {
+                Some(cup) if cup != 0 && !cups.contains(&cup) => cups.push(cup),
+                _ => return Err(line.error_at(idx, format!("invalid cup '{}'", c))),
+            }
+        }
+        // Each move needs the current cup, the three picked up and somewhere else to put them
+        if cups.len() < MIN_CUPS {
+            return Err(line.error(format!(
+                "expected at least {} cups, found {}",
+                MIN_CUPS,
+                cups.len()
+            )));
+        }
+        // The labels must be 1 to the number of cups, with none missing
+        if cups.iter().any(|cup| *cup as usize > cups.len()) {
+            return Err(line.error(format!("expected the cups 1 to {}", cups.len())));
+        }
+        Ok(cups)
+    }
+
+    fn part1(start: &Self::Input) -> Answer {
+        play(start, start.len(), 100)
+            .after_one()
+            .map(|cup| cup.to_string())
+            .collect::<String>()
+            .into()
+    }
+
+    fn part2(start: &Self::Input) -> Answer {
+        let cups = play(start, 1_000_000, 10_000_000);
+        let mut stars = cups.after_one();
+        let first = stars.next().expect("There are a million cups");
+        let second = stars.next().expect("There are a million cups");
+        (first as u64 * second as u64).into()
+    }
+}
+
+#[test]
+fn test_example() {
+    let start = Day23::parse(crate::input::from_str("389125467")).unwrap();
+    assert_eq!(
+        play(&start, start.len(), 10)
+            .after_one()
+            .collect::<Vec<_>>(),
+        [9, 2, 6, 5, 8, 3, 7, 4]
+    );
+    assert_eq!(Day23::part1(&start), "67384529");
+    assert_eq!(Day23::part2(&start), 149245887792);
+}
+
+#[test]
+fn test_too_few_cups() {
+    let err = Day23::parse(crate::input::from_str("123")).err().unwrap();
+    assert_eq!(err.to_string(), "<input>:1: expected at least 5 cups, found 3");
+    assert!(Day23::parse(crate::input::from_str("")).is_err());
+}
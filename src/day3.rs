@@ -1,32 +1,59 @@
-fn load_data() -> Vec<Vec<bool>> {
-    crate::util::data_lines(3).map(|line| {
-        line.chars().map(|c| {
-            match c {
-                '.' => false,
-                '#' => true,
-                _ => unreachable!(),
-            }
-        }).collect()
-    }).collect()
-}
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::grid::Grid;
 
-fn perform_traverse(right: usize, down: usize, data: &[Vec<bool>]) -> usize {
-    data.iter().step_by(down).enumerate().filter(|(row_id, row_data)| {
-        row_data[(row_id * right) % row_data.len()]
+fn perform_traverse(right: usize, down: usize, data: &Grid<bool>) -> usize {
+    (0..data.height()).step_by(down).enumerate().filter(|(step, y)| {
+        *data.get_wrapping((step * right) as isize, *y as isize)
     }).count()
 }
 
-pub fn part1() {
-    let data = load_data();
-    println!("Day 3, Part 1: {}", perform_traverse(3, 1, &data));
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: usize = 3;
+
+    type Input = Grid<bool>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        Grid::parse(lines, |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        perform_traverse(3, 1, data).into()
+    }
+
+    fn part2(data: &Self::Input) -> Answer {
+        let path1 = perform_traverse(1, 1, data);
+        let path2 = perform_traverse(3, 1, data);
+        let path3 = perform_traverse(5, 1, data);
+        let path4 = perform_traverse(7, 1, data);
+        let path5 = perform_traverse(1, 2, data);
+        (path1 * path2 * path3 * path4 * path5).into()
+    }
 }
 
-pub fn part2() {
-    let data = load_data();
-    let path1 = perform_traverse(1, 1, &data);
-    let path2 = perform_traverse(3, 1, &data);
-    let path3 = perform_traverse(5, 1, &data);
-    let path4 = perform_traverse(7, 1, &data);
-    let path5 = perform_traverse(1, 2, &data);
-    println!("Day 3, Part 2: {}", path1 * path2 * path3 * path4 * path5);
+#[test]
+fn test_example() {
+    let data = Day3::parse(crate::input::from_str(
+        "..##.......
+#...#...#..
+.#....#..#.
+..#.#...#.#
+.#...##..#.
+..#.##.....
+.#.#.#....#
+.#........#
+#.##...#...
+#...##....#
+.#..#...#.#",
+    ))
+    .unwrap();
+    assert_eq!(Day3::part1(&data), 7);
+    assert_eq!(Day3::part2(&data), 336);
 }
@@ -1,24 +1,11 @@
 use std::collections::HashMap;
 
-struct Passport(HashMap<String, String>);
-
-fn load_data() -> Vec<Passport> {
-    let mut res = Vec::new();
-    let mut current = HashMap::new();
-    for line in crate::util::data_lines(4) {
-        if line.trim().is_empty() {
-            res.push(Passport(current));
-            current = HashMap::new();
-        } else {
-            current.extend(line.split(" ").map(|entry| {
-                let (key, value) = <str as crate::util::StrExt>::split_once(entry, ":");
-                (key.to_string(), value.to_string())
-            }))
-        }
-    }
-    res.push(Passport(current));
-    res
-}
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::parse::Field;
+
+pub struct Passport(HashMap<String, String>);
 
 macro_rules! o {
     ($exp:expr) => {
@@ -59,36 +46,116 @@ impl Passport {
         let hcl = o!(self.0.get("hcl"));
         let ecl = o!(self.0.get("ecl"));
         let pid = o!(self.0.get("pid"));
-        let byr_valid = (1920 <= byr) && (byr <= 2002);
-        let iyr_valid = (2010 <= iyr) && (iyr <= 2020);
-        let eyr_valid = (2020 <= eyr) && (eyr <= 2030);
+        let byr_valid = (1920..=2002).contains(&byr);
+        let iyr_valid = (2010..=2020).contains(&iyr);
+        let eyr_valid = (2020..=2030).contains(&eyr);
         let hgt_valid = self.validate_hgt(hgt);
-        let hcl_valid = (hcl.len() == 7) && (hcl.chars().nth(0).unwrap() == '#') && (hcl[1..].chars().all(|c| (c >= '0' && c <= '9') || (c >= 'a' && c <= 'f')));
+        let hcl_valid = (hcl.len() == 7) && (hcl.chars().nth(0).unwrap() == '#') && (hcl[1..].chars().all(|c| c.is_ascii_digit() || ('a'..='f').contains(&c)));
         let ecl_valid = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"].iter().any(|c| c == ecl);
-        let pid_valid = (pid.len() == 9) && (pid.chars().all(|c| (c >= '0' && c <= '9')));
+        let pid_valid = (pid.len() == 9) && (pid.chars().all(|c| c.is_ascii_digit()));
         byr_valid && iyr_valid && eyr_valid && hgt_valid && hcl_valid && ecl_valid && pid_valid
     }
 
     fn validate_hgt(&self, hgt: &str) -> bool {
         use std::str::FromStr as _;
-        if hgt.ends_with("in") {
-            let hgt = r!(usize::from_str(&hgt[..hgt.len() - 2]));
-            (59 <= hgt) && (hgt <= 76)
-        } else if hgt.ends_with("cm") {
-            let hgt = r!(usize::from_str(&hgt[..hgt.len() - 2]));
-            (150 <= hgt) && (hgt <= 193)
+        if let Some(hgt) = hgt.strip_suffix("in") {
+            let hgt = r!(usize::from_str(hgt));
+            (59..=76).contains(&hgt)
+        } else if let Some(hgt) = hgt.strip_suffix("cm") {
+            let hgt = r!(usize::from_str(hgt));
+            (150..=193).contains(&hgt)
         } else {
             false
         }
     }
 }
 
-pub fn part1() {
-    let data = load_data();
-    println!("Day 4, Part 1: {}", data.iter().filter(|p| p.validate_old()).count());
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: usize = 4;
+
+    type Input = Vec<Passport>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        crate::util::records(lines)
+            .map(|record| {
+                let mut fields = HashMap::new();
+                for line in &record? {
+                    for entry in Field::new(line).split(" ") {
+                        let (key, value) = entry.split_once(":")?;
+                        fields.insert(key.to_string(), value.to_string());
+                    }
+                }
+                Ok(Passport(fields))
+            })
+            .collect()
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        data.iter().filter(|p| p.validate_old()).count().into()
+    }
+
+    fn part2(data: &Self::Input) -> Answer {
+        data.iter().filter(|p| p.validate_new()).count().into()
+    }
 }
 
-pub fn part2() {
-    let data = load_data();
-    println!("Day 4, Part 2: {}", data.iter().filter(|p| p.validate_new()).count());
+#[test]
+fn test_example_part1() {
+    let data = Day4::parse(crate::input::from_str(
+        "ecl:gry pid:860033327 eyr:2020 hcl:#fffffd
+byr:1937 iyr:2017 cid:147 hgt:183cm
+
+iyr:2013 ecl:amb cid:350 eyr:2023 pid:028048884
+hcl:#cfa07d byr:1929
+
+hcl:#ae17e1 iyr:2013
+eyr:2024
+ecl:brn pid:760753108 byr:1931
+hgt:179cm
+
+hcl:#cfa07d eyr:2025 pid:166559648
+iyr:2011 ecl:brn hgt:59in",
+    ))
+    .unwrap();
+    assert_eq!(Day4::part1(&data), 2);
+}
+
+#[test]
+fn test_example_part2() {
+    let invalid = Day4::parse(crate::input::from_str(
+        "eyr:1972 cid:100
+hcl:#18171d ecl:amb hgt:170 pid:186cm iyr:2018 byr:1926
+
+iyr:2019
+hcl:#602927 eyr:1967 hgt:170cm
+ecl:grn pid:012533040 byr:1946
+
+hcl:dab227 iyr:2012
+ecl:brn hgt:182cm pid:021572410 eyr:2020 byr:1992 cid:277
+
+hgt:59cm ecl:zzz
+eyr:2038 hcl:74454a iyr:2023
+pid:3556412378 byr:2007",
+    ))
+    .unwrap();
+    assert_eq!(Day4::part2(&invalid), 0);
+
+    let valid = Day4::parse(crate::input::from_str(
+        "pid:087499704 hgt:74in ecl:grn iyr:2012 eyr:2030 byr:1980
+hcl:#623a2f
+
+eyr:2029 ecl:blu cid:129 byr:1989
+iyr:2014 pid:896056539 hcl:#a97842 hgt:165cm
+
+hcl:#888785
+hgt:164cm byr:2001 iyr:2015 cid:88
+pid:545766238 ecl:hzl
+eyr:2022
+
+iyr:2010 hgt:158cm hcl:#b6652a ecl:blu byr:1944 eyr:2021 pid:093154719",
+    ))
+    .unwrap();
+    assert_eq!(Day4::part2(&valid), 4);
 }
@@ -1,24 +1,49 @@
-fn get_seat_ids() -> Vec<usize> {
-    crate::util::data_lines(5).map(|line| {
-        line.chars().fold(0, |acc, c| {
-            match c {
-                'B' | 'R' => acc * 2 + 1,
-                'F' | 'L' => acc * 2,
-                _ => unreachable!(),
-            }
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: usize = 5;
+
+    type Input = Vec<usize>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| {
+            line.char_indices().try_fold(0, |acc, (idx, c)| {
+                match c {
+                    'B' | 'R' => Ok(acc * 2 + 1),
+                    'F' | 'L' => Ok(acc * 2),
+                    _ => Err(line.error_at(idx, format!("unexpected character '{}'", c))),
+                }
+            })
         })
-    }).collect()
-}
+    }
+
+    fn part1(seat_ids: &Self::Input) -> Answer {
+        (*seat_ids.iter().max().expect("There are no boarding passes")).into()
+    }
 
-pub fn part1() {
-    println!("Day 5, Part 1: {}", get_seat_ids().into_iter().max().unwrap());
+    fn part2(seat_ids: &Self::Input) -> Answer {
+        let min = seat_ids.iter().min().expect("There are no boarding passes");
+        let max = seat_ids.iter().max().expect("There are no boarding passes");
+        let total: usize = seat_ids.iter().sum();
+        let expected_total = (max - min + 1) * (max + min) / 2;
+        (expected_total - total).into()
+    }
 }
 
-pub fn part2() {
-    let seat_ids = get_seat_ids();
-    let min = seat_ids.iter().min().unwrap();
-    let max = seat_ids.iter().max().unwrap();
-    let total: usize = seat_ids.iter().sum();
-    let expected_total = (max - min + 1) * (max + min) / 2;
-    println!("Day 5, Part 2: {}", expected_total - total);
+#[test]
+fn test_example_part1() {
+    let seat_ids = Day5::parse(crate::input::from_str(
+        "BFFFBBFRRR\nFFFBBBFRRR\nBBFFBBFRLL",
+    ))
+    .unwrap();
+    assert_eq!(seat_ids, [567, 119, 820]);
+    assert_eq!(Day5::part1(&seat_ids), 820);
 }
+
+#[test]
+#[ignore = "the puzzle has no worked example for part 2"]
+fn test_example_part2() {}
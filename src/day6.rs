@@ -1,33 +1,57 @@
 use std::collections::HashSet;
 
-fn load_data() -> Vec<Vec<HashSet<char>>> {
-    let mut res = Vec::new();
-    let mut current = Vec::new();
-    for line in crate::util::data_lines(6) {
-        if line.trim().is_empty() {
-            res.push(current);
-            current = Vec::new();
-        } else {
-            let answers: HashSet<char> = line.chars().collect();
-            current.push(answers);
-        }
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: usize = 6;
+
+    type Input = Vec<Vec<HashSet<char>>>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        crate::util::records(lines)
+            .map(|record| Ok(record?.iter().map(|line| line.chars().collect()).collect()))
+            .collect()
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        use itertools::Itertools as _;
+        data.iter().map(|group| {
+            group.iter().cloned().tree_fold1(|a: HashSet<char>, b: HashSet<char>| -> HashSet<char> { a.union(&b).cloned().collect::<HashSet<_>>() }).unwrap().len()
+        }).sum::<usize>().into()
     }
-    res.push(current);
-    res
-}
 
-pub fn part1() {
-    use itertools::Itertools as _;
-    let data = load_data();
-    println!("Day 6, Part 1: {}", data.into_iter().map(|group| {
-        group.into_iter().tree_fold1(|a: HashSet<char>, b: HashSet<char>| -> HashSet<char> { a.union(&b).cloned().collect::<HashSet<_>>() }).unwrap().len()
-    }).sum::<usize>());
+    fn part2(data: &Self::Input) -> Answer {
+        use itertools::Itertools as _;
+        data.iter().map(|group| {
+            group.iter().cloned().tree_fold1(|a: HashSet<char>, b: HashSet<char>| -> HashSet<char> { a.intersection(&b).cloned().collect::<HashSet<_>>() }).unwrap().len()
+        }).sum::<usize>().into()
+    }
 }
 
-pub fn part2() {
-    use itertools::Itertools as _;
-    let data = load_data();
-    println!("Day 6, Part 2: {}", data.into_iter().map(|group| {
-        group.into_iter().tree_fold1(|a: HashSet<char>, b: HashSet<char>| -> HashSet<char> { a.intersection(&b).cloned().collect::<HashSet<_>>() }).unwrap().len()
-    }).sum::<usize>());
+#[test]
+fn test_example() {
+    let data = Day6::parse(crate::input::from_str(
+        "abc
+
+a
+b
+c
+
+ab
+ac
+
+a
+a
+a
+a
+
+b",
+    ))
+    .unwrap();
+    assert_eq!(Day6::part1(&data), 11);
+    assert_eq!(Day6::part2(&data), 6);
 }
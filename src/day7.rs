@@ -1,61 +1,126 @@
 use std::collections::{HashMap, HashSet};
 
-struct Trees {
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::parse::Field;
+
+pub struct Trees {
     forward: HashMap<String, HashSet<(usize, String)>>,
     backward: HashMap<String, HashSet<String>>,
 }
 
-fn load_data() -> Trees {
-    let mut backward = HashMap::<_, HashSet<_>>::new();
-    let forward = crate::util::data_lines(7).map(|line| {
-        let (outer, rest) = <str as crate::util::StrExt>::split_once(&line, " bags contain ");
-        if rest == "no other bags." {
-            (outer.to_string(), HashSet::new())
-        } else {
-            let rest = rest.trim_end_matches('.');
-            let inner = rest.split(", ").map(|term| {
-                let term = term.trim_end_matches('s');
-                let term = term.trim_end_matches(" bag");
-                let (count, colour) = <str as crate::util::StrExt>::split_once(term, " ");
-                backward.entry(colour.to_string()).or_default().insert(outer.to_string());
-                (<usize as std::str::FromStr>::from_str(count).unwrap(), colour.to_string())
-            }).collect();
-            (outer.to_string(), inner)
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: usize = 7;
+
+    type Input = Trees;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        let mut backward = HashMap::<_, HashSet<_>>::new();
+        let rules = lines.map(|line| {
+            let line = line?;
+            let (outer, inner) = {
+                let (outer, rest) = Field::new(&line).split_once(" bags contain ")?;
+                if &*rest == "no other bags." {
+                    (outer.to_string(), HashSet::new())
+                } else {
+                    let rest = rest.strip_suffix(".")?;
+                    let inner = rest.split(", ").map(|term| {
+                        let term = term.strip_suffix(" bags").or_else(|_| term.strip_suffix(" bag"))?;
+                        let (count, colour) = term.split_once(" ")?;
+                        backward.entry(colour.to_string()).or_default().insert(outer.to_string());
+                        Ok((count.parse()?, colour.to_string()))
+                    }).collect::<Result<_>>()?;
+                    (outer.to_string(), inner)
+                }
+            };
+            Ok((line, outer, inner))
+        }).collect::<Result<Vec<_>>>()?;
+
+        // Every bag inside another must have its own rule, saying what's inside it in turn
+        let defined = rules.iter().map(|(_, outer, _)| outer.as_str()).collect::<HashSet<_>>();
+        for (line, _, inner) in &rules {
+            if let Some((_, colour)) = inner.iter().find(|(_, colour)| !defined.contains(colour.as_str())) {
+                return Err(line.error(format!("no rule for {} bags", colour)));
+            }
         }
-    }).collect();
-    Trees {
-        forward,
-        backward,
+
+        let forward = rules.into_iter().map(|(_, outer, inner)| (outer, inner)).collect();
+        Ok(Trees {
+            forward,
+            backward,
+        })
     }
-}
 
-pub fn part1() {
-    let data = load_data();
-    let mut processed = HashSet::new();
-    processed.insert("shiny gold".to_string());
-    let mut to_process = vec!["shiny gold".to_string()];
-    while let Some(current) = to_process.pop() {
-        if let Some(next_colours) = data.backward.get(&current) {
-            for colour in next_colours {
-                if !processed.contains(colour) {
-                    to_process.push(colour.clone());
-                    processed.insert(colour.clone());
+    fn part1(data: &Self::Input) -> Answer {
+        let mut processed = HashSet::new();
+        processed.insert("shiny gold".to_string());
+        let mut to_process = vec!["shiny gold".to_string()];
+        while let Some(current) = to_process.pop() {
+            if let Some(next_colours) = data.backward.get(&current) {
+                for colour in next_colours {
+                    if !processed.contains(colour) {
+                        to_process.push(colour.clone());
+                        processed.insert(colour.clone());
+                    }
                 }
             }
         }
+        (processed.len() - 1).into() // Subtract 1 because we primed the set with shiny gold which isn't a valid outer colour
     }
-    println!("Day 7, Part 1: {}", processed.len() - 1); // Subtract 1 because we primed the set with shiny gold which isn't a valid outer colour
-}
 
-pub fn part2() {
-    let data = load_data();
+    fn part2(data: &Self::Input) -> Answer {
+        fn count_inner_bags(trees: &Trees, name: &str) -> usize {
+            trees.forward[name].iter().fold(0, |acc, (count, name)| {
+                acc + count * count_inner_bags(trees, name) + count
+            })
+        }
 
-    fn count_inner_bags(trees: &Trees, name: &str) -> usize {
-        trees.forward[name].iter().fold(0, |acc, (count, name)| {
-            acc + count * count_inner_bags(trees, name) + count
-        })
+        count_inner_bags(data, "shiny gold").into()
     }
+}
+
+#[test]
+fn test_example() {
+    let data = Day7::parse(crate::input::from_str(
+        "light red bags contain 1 bright white bag, 2 muted yellow bags.
+dark orange bags contain 3 bright white bags, 4 muted yellow bags.
+bright white bags contain 1 shiny gold bag.
+muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.
+shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.
+dark olive bags contain 3 faded blue bags, 4 dotted black bags.
+vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+faded blue bags contain no other bags.
+dotted black bags contain no other bags.",
+    ))
+    .unwrap();
+    assert_eq!(Day7::part1(&data), 4);
+    assert_eq!(Day7::part2(&data), 32);
+}
 
-    println!("Day 7, Part 2: {}", count_inner_bags(&data, "shiny gold"));
+#[test]
+fn test_example_part2_deep() {
+    let data = Day7::parse(crate::input::from_str(
+        "shiny gold bags contain 2 dark red bags.
+dark red bags contain 2 dark orange bags.
+dark orange bags contain 2 dark yellow bags.
+dark yellow bags contain 2 dark green bags.
+dark green bags contain 2 dark blue bags.
+dark blue bags contain 2 dark violet bags.
+dark violet bags contain no other bags.",
+    ))
+    .unwrap();
+    assert_eq!(Day7::part2(&data), 126);
 }
 
+#[test]
+fn test_undefined_colour() {
+    let err = Day7::parse(crate::input::from_str(
+        "shiny gold bags contain 2 dark red bags.\ndark red bags contain 1 pale blue bag.",
+    ))
+    .err()
+    .unwrap();
+    assert_eq!(err.to_string(), "<input>:2: no rule for pale blue bags");
+}
@@ -1,34 +1,18 @@
 use std::rc::Rc;
 
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+use crate::util::parse::Field;
+
 #[derive(Debug, Clone, Copy)]
-enum Op {
+pub enum Op {
     Nop(isize),
     Acc(isize),
     Jmp(isize),
     Term,
 }
 
-fn load_data() -> Rc<[Op]> {
-    let mut program = crate::util::data_lines(8)
-        .map(|line| {
-            use std::str::FromStr as _;
-            let (op, sign, num) = (&line[..3], &line.as_bytes()[3], &line[4..]);
-            let mut arg = isize::from_str(num).unwrap();
-            if *sign == b'-' {
-                arg *= -1;
-            }
-            match op {
-                "nop" => Op::Nop(arg),
-                "acc" => Op::Acc(arg),
-                "jmp" => Op::Jmp(arg),
-                _ => unreachable!("{:?}", line),
-            }
-        })
-        .collect::<Vec<_>>();
-    program.push(Op::Term);
-    program.into()
-}
-
 struct State {
     pc: isize,
     acc: isize,
@@ -108,25 +92,57 @@ impl State {
     }
 }
 
-pub fn part1() {
-    let program = load_data();
-    let state = State::new(program);
-    match state.run() {
-        PostRun::LiveLock(acc) => println!("Day 8, Part 1: {}", acc),
-        PostRun::Termination(_) => unreachable!(),
-    };
-}
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: usize = 8;
+
+    type Input = Rc<[Op]>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        let mut program = lines.parse_each(|line| {
+            let (op, arg) = Field::new(line).split_once(" ")?;
+            let arg = arg.parse_signed()?;
+            match &*op {
+                "nop" => Ok(Op::Nop(arg)),
+                "acc" => Ok(Op::Acc(arg)),
+                "jmp" => Ok(Op::Jmp(arg)),
+                _ => Err(op.error(format!("unknown operation '{}'", &*op))),
+            }
+        })?;
+        program.push(Op::Term);
+        Ok(program.into())
+    }
 
-pub fn part2() {
-    let program = load_data();
-    let program_length = program.len();
-    for idx in 0..program_length {
-        let mut state = State::new(Rc::clone(&program));
-        state.tweak(idx as isize);
-        if let PostRun::Termination(acc) = state.run() {
-            println!("Day 8, Part 2: {}", acc);
-            return;
+    fn part1(program: &Self::Input) -> Answer {
+        let state = State::new(Rc::clone(program));
+        match state.run() {
+            PostRun::LiveLock(acc) => acc.into(),
+            PostRun::Termination(_) => {
+                panic!("The program terminates without repeating an instruction")
+            }
         }
     }
-    unreachable!("Didn't find an answer");
+
+    fn part2(program: &Self::Input) -> Answer {
+        let program_length = program.len();
+        for idx in 0..program_length {
+            let mut state = State::new(Rc::clone(program));
+            state.tweak(idx as isize);
+            if let PostRun::Termination(acc) = state.run() {
+                return acc.into();
+            }
+        }
+        panic!("No single nop/jmp swap makes the program terminate");
+    }
+}
+
+#[test]
+fn test_example() {
+    let program = Day8::parse(crate::input::from_str(
+        "nop +0\nacc +1\njmp +4\nacc +3\njmp -3\nacc -99\nacc +1\njmp -4\nacc +6",
+    ))
+    .unwrap();
+    assert_eq!(Day8::part1(&program), 5);
+    assert_eq!(Day8::part2(&program), 8);
 }
@@ -1,43 +1,39 @@
-fn load_data() -> Vec<usize> {
-    crate::util::data_lines(9)
-        .map(|line| std::str::FromStr::from_str(&line))
-        .map(Result::unwrap)
-        .collect()
-}
+use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
 
-pub fn part1() {
-    for window in load_data().windows(26) {
-        let (preamble, target) = (&window[..25], window[25]);
+/// Find the first number that isn't the sum of two of the `preamble` numbers before it
+fn first_invalid(data: &[usize], preamble: usize) -> usize {
+    for window in data.windows(preamble + 1) {
+        let (preamble, target) = (&window[..preamble], window[preamble]);
         if crate::util::find_pair(preamble, target).is_none() {
-            println!("Day 9, Part 1: {}", target);
-            break;
+            return target;
         }
     }
+    panic!("Every number is the sum of two of the {} before it", preamble);
 }
 
-pub fn part2() {
+/// Find the contiguous range (of at least two numbers) summing to `target`, and add together its
+/// smallest and largest numbers
+fn encryption_weakness(data: &[usize], target: usize) -> usize {
     let mut low = 0;
     let mut high = 1;
     enum Parity {
         Wax,
         Wane,
-    };
+    }
     let mut parity = Parity::Wax;
-    let data = load_data();
     loop {
+        assert!(high < data.len(), "No contiguous range of numbers sums to {}", target);
         let range = &data[low..=high];
-        match (&parity, range.iter().sum::<usize>().cmp(&22406676)) {
+        match (&parity, range.iter().sum::<usize>().cmp(&target)) {
             (Parity::Wax, std::cmp::Ordering::Less) => high += 1,
             (Parity::Wane, std::cmp::Ordering::Less) => {
                 low += 1;
                 parity = Parity::Wax;
             }
             (_, std::cmp::Ordering::Equal) => {
-                println!(
-                    "Day 9, Part 2: {}",
-                    range.iter().min().unwrap() + range.iter().max().unwrap()
-                );
-                break;
+                return range.iter().min().unwrap() + range.iter().max().unwrap();
             }
             (Parity::Wax, std::cmp::Ordering::Greater) => {
                 low += 1;
@@ -49,3 +45,33 @@ pub fn part2() {
         }
     }
 }
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: usize = 9;
+
+    type Input = Vec<usize>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| line.parse_as())
+    }
+
+    fn part1(data: &Self::Input) -> Answer {
+        first_invalid(data, 25).into()
+    }
+
+    fn part2(data: &Self::Input) -> Answer {
+        encryption_weakness(data, first_invalid(data, 25)).into()
+    }
+}
+
+#[test]
+fn test_example() {
+    let data = Day9::parse(crate::input::from_str(
+        "35\n20\n15\n25\n47\n40\n62\n55\n65\n95\n102\n117\n150\n182\n127\n219\n299\n277\n309\n576",
+    ))
+    .unwrap();
+    assert_eq!(first_invalid(&data, 5), 127);
+    assert_eq!(encryption_weakness(&data, 127), 62);
+}
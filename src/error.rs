@@ -0,0 +1,65 @@
+/// An error encountered while fetching, reading, parsing or solving a puzzle input
+#[derive(Debug)]
+pub enum Error {
+    /// The input couldn't be read
+    Io {
+        name: String,
+        error: std::io::Error,
+    },
+    /// The input was read, but isn't in the expected format
+    Parse {
+        name: String,
+        /// The (1-based) line the problem was found on
+        line: usize,
+        /// The (1-based) column the problem was found at, if it's that specific
+        column: Option<usize>,
+        message: String,
+    },
+    /// The input couldn't be downloaded
+    Fetch {
+        url: String,
+        message: String,
+    },
+    /// The solution panicked
+    Panic { message: String },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Whether this is because the puzzle input doesn't exist (e.g. it hasn't been downloaded yet)
+    pub fn is_missing_input(&self) -> bool {
+        matches!(self, Error::Io { error, .. } if error.kind() == std::io::ErrorKind::NotFound)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Io { name, error } => write!(f, "{}: {}", name, error),
+            Error::Parse {
+                name,
+                line,
+                column: Some(column),
+                message,
+            } => write!(f, "{}:{}:{}: {}", name, line, column, message),
+            Error::Parse {
+                name,
+                line,
+                column: None,
+                message,
+            } => write!(f, "{}:{}: {}", name, line, message),
+            Error::Fetch { url, message } => write!(f, "{}: {}", url, message),
+            Error::Panic { message } => write!(f, "panicked: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { error, .. } => Some(error),
+            Error::Parse { .. } | Error::Fetch { .. } | Error::Panic { .. } => None,
+        }
+    }
+}
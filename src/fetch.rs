@@ -0,0 +1,158 @@
+use std::path::PathBuf;
+
+use crate::error::{Error, Result};
+use crate::input::{DataDir, InputProvider, Lines};
+
+/// Somewhere puzzle inputs can be downloaded from
+pub trait Fetcher: Sync {
+    /// Download the puzzle input for the given day
+    fn fetch(&self, day: usize) -> Result<String>;
+}
+
+/// Downloads puzzle inputs from the Advent of Code website (or something pretending to be it)
+pub struct Http {
+    /// Where the site lives, e.g. `https://adventofcode.com`
+    pub base_url: String,
+    /// The value of the `session` cookie of a logged-in user
+    pub session: String,
+}
+
+impl Http {
+    pub const DEFAULT_BASE_URL: &'static str = "https://adventofcode.com";
+
+    pub fn new(session: &str) -> Self {
+        Http {
+            base_url: Self::DEFAULT_BASE_URL.to_string(),
+            session: session.to_string(),
+        }
+    }
+}
+
+impl Fetcher for Http {
+    fn fetch(&self, day: usize) -> Result<String> {
+        let url = format!("{}/2020/day/{}/input", self.base_url.trim_end_matches('/'), day);
+        let error = |message: String| Error::Fetch {
+            url: url.clone(),
+            message,
+        };
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call()
+            .map_err(|err| match err {
+                ureq::Error::Status(status, response) => {
+                    error(format!("{} {}", status, response.status_text()))
+                }
+                ureq::Error::Transport(err) => error(err.to_string()),
+            })?;
+        response
+            .into_string()
+            .map_err(|err| error(format!("couldn't read the response: {}", err)))
+    }
+}
+
+/// Reads each day's input from a directory like `DataDir`, first downloading any that aren't
+/// there yet (or are empty)
+pub struct Cached<F: Fetcher> {
+    pub dir: PathBuf,
+    pub fetcher: F,
+}
+
+impl<F: Fetcher> InputProvider for Cached<F> {
+    fn lines(&self, day: usize) -> Result<Lines> {
+        let path = self.dir.join(format!("day{}.data", day));
+        // An empty file is a placeholder (e.g. from `new-day`), not a downloaded input
+        let cached = std::fs::metadata(&path).is_ok_and(|meta| meta.len() > 0);
+        if !cached {
+            let input = self.fetcher.fetch(day)?;
+            let io_error = |error| Error::Io {
+                name: path.display().to_string(),
+                error,
+            };
+            // Write to a temporary file first, so an interrupted download isn't mistaken for an
+            // input later
+            let partial = path.with_extension("data.partial");
+            std::fs::create_dir_all(&self.dir).map_err(io_error)?;
+            std::fs::write(&partial, input).map_err(io_error)?;
+            std::fs::rename(&partial, &path).map_err(io_error)?;
+        }
+        DataDir(self.dir.clone()).lines(day)
+    }
+}
+
+/// Serve a single request for a puzzle input, so a second download would fail to connect.
+/// Returns the server's base URL and a handle that gives the request's lines once it's served.
+#[cfg(test)]
+fn serve_once(body: &'static str) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    use std::io::{BufRead as _, BufReader, Write as _};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        for line in BufReader::new(&stream).lines() {
+            let line = line.unwrap();
+            if line.is_empty() {
+                break;
+            }
+            request.push(line);
+        }
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        (&stream).write_all(response.as_bytes()).unwrap();
+        request
+    });
+    (base_url, server)
+}
+
+#[cfg(test)]
+fn read_all(provider: &dyn InputProvider) -> Vec<String> {
+    provider
+        .lines(1)
+        .unwrap()
+        .map(|line| line.unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_fetch_into_cache() {
+    let (base_url, server) = serve_once("1721\n979\n");
+    let dir = std::env::temp_dir().join(format!("aoc2020-fetch-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let provider = Cached {
+        dir: dir.clone(),
+        fetcher: Http {
+            base_url,
+            session: "cafe".to_string(),
+        },
+    };
+    assert_eq!(read_all(&provider), ["1721", "979"]);
+    let request = server.join().unwrap();
+    assert_eq!(request[0], "GET /2020/day/1/input HTTP/1.1");
+    assert!(request.iter().any(|header| header == "Cookie: session=cafe"));
+
+    assert_eq!(read_all(&provider), ["1721", "979"]);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_fetch_replaces_empty_placeholder() {
+    let (base_url, server) = serve_once("1721\n");
+    let dir = std::env::temp_dir().join(format!("aoc2020-placeholder-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("day1.data"), "").unwrap();
+    let provider = Cached {
+        dir: dir.clone(),
+        fetcher: Http {
+            base_url,
+            session: "cafe".to_string(),
+        },
+    };
+    assert_eq!(read_all(&provider), ["1721"]);
+    server.join().unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+}
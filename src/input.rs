@@ -0,0 +1,208 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::error::{Error, Result};
+
+/// Somewhere puzzle inputs can be read from (possibly by several threads at once)
+pub trait InputProvider: Sync {
+    /// Start reading the puzzle input for the given day
+    fn lines(&self, day: usize) -> Result<Lines>;
+}
+
+/// Reads each day's input from `day{N}.data` in a directory (by default, `data/`)
+pub struct DataDir(pub PathBuf);
+
+impl Default for DataDir {
+    fn default() -> Self {
+        DataDir(PathBuf::from("data"))
+    }
+}
+
+impl InputProvider for DataDir {
+    fn lines(&self, day: usize) -> Result<Lines> {
+        File(self.0.join(format!("day{}.data", day))).lines(day)
+    }
+}
+
+/// Reads a single file, whatever the day
+pub struct File(pub PathBuf);
+
+impl InputProvider for File {
+    fn lines(&self, _day: usize) -> Result<Lines> {
+        use std::io::BufRead as _;
+        let name = self.0.display().to_string();
+        let file = std::fs::File::open(&self.0).map_err(|error| Error::Io {
+            name: name.clone(),
+            error,
+        })?;
+        let file = std::io::BufReader::new(file);
+        Ok(Lines::new(&name, file.lines()))
+    }
+}
+
+/// Reads the input from standard input, whatever the day
+pub struct Stdin;
+
+impl InputProvider for Stdin {
+    fn lines(&self, _day: usize) -> Result<Lines> {
+        use std::io::BufRead as _;
+        let stdin = std::io::BufReader::new(std::io::stdin());
+        Ok(Lines::new("<stdin>", stdin.lines()))
+    }
+}
+
+/// An input held in memory, e.g. a worked example from the puzzle text
+pub struct Text(pub String);
+
+impl InputProvider for Text {
+    fn lines(&self, _day: usize) -> Result<Lines> {
+        Ok(from_str(&self.0))
+    }
+}
+
+/// Split an in-memory input into lines
+pub fn from_str(text: &str) -> Lines {
+    Lines::new(
+        "<input>",
+        text.lines()
+            .map(|line| Ok(line.to_string()))
+            .collect::<Vec<_>>()
+            .into_iter(),
+    )
+}
+
+/// The lines of a puzzle input, ready to be handed to `Solution::parse`.
+///
+/// Each line remembers where it came from, so parse errors can say where the problem is.
+pub struct Lines {
+    name: Arc<str>,
+    inner: Box<dyn Iterator<Item = std::io::Result<String>>>,
+    number: usize,
+}
+
+impl Lines {
+    pub fn new(
+        name: &str,
+        inner: impl Iterator<Item = std::io::Result<String>> + 'static,
+    ) -> Self {
+        Lines {
+            name: name.into(),
+            inner: Box::new(inner),
+            number: 0,
+        }
+    }
+
+    /// Build an error for the line after the last one read, e.g. because the input stopped early
+    pub fn error(&self, message: impl std::fmt::Display) -> Error {
+        Error::Parse {
+            name: self.name.to_string(),
+            line: self.number + 1,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Read the next line, treating the end of the input as an error
+    pub fn next_line(&mut self) -> Result<Line> {
+        match self.next() {
+            Some(line) => line,
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Read the rest of the input into memory, so it can be parsed repeatedly
+    pub fn buffer(self) -> Result<Buffered> {
+        let name = Arc::clone(&self.name);
+        let lines = self
+            .map(|line| line.map(|line| line.text))
+            .collect::<Result<_>>()?;
+        Ok(Buffered { name, lines })
+    }
+
+    /// Parse every remaining line with the same function
+    pub fn parse_each<T>(self, parse: impl FnMut(&Line) -> Result<T>) -> Result<Vec<T>> {
+        let mut parse = parse;
+        self.map(|line| parse(&line?)).collect()
+    }
+}
+
+impl Iterator for Lines {
+    type Item = Result<Line>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = self.inner.next()?;
+        self.number += 1;
+        Some(match line {
+            Ok(text) => Ok(Line {
+                text,
+                number: self.number,
+                name: Arc::clone(&self.name),
+            }),
+            Err(error) => Err(Error::Io {
+                name: self.name.to_string(),
+                error,
+            }),
+        })
+    }
+}
+
+/// A puzzle input that's been read into memory
+pub struct Buffered {
+    name: Arc<str>,
+    lines: Vec<String>,
+}
+
+impl Buffered {
+    /// Start reading the input again from the top
+    pub fn lines(&self) -> Lines {
+        Lines::new(&self.name, self.lines.clone().into_iter().map(Ok))
+    }
+}
+
+/// One line of a puzzle input.  Dereferences to the line's text.
+pub struct Line {
+    text: String,
+    number: usize,
+    name: Arc<str>,
+}
+
+impl Line {
+    /// Build an error about this line
+    pub fn error(&self, message: impl std::fmt::Display) -> Error {
+        Error::Parse {
+            name: self.name.to_string(),
+            line: self.number,
+            column: None,
+            message: message.to_string(),
+        }
+    }
+
+    /// Build an error about the character at the given (0-based) byte offset into this line
+    pub fn error_at(&self, offset: usize, message: impl std::fmt::Display) -> Error {
+        Error::Parse {
+            name: self.name.to_string(),
+            line: self.number,
+            column: Some(offset + 1),
+            message: message.to_string(),
+        }
+    }
+
+    /// Parse the whole line as a single value
+    pub fn parse_as<T>(&self) -> Result<T>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.text
+            .parse()
+            .map_err(|err| self.error(format!("invalid value '{}': {}", self.text, err)))
+    }
+}
+
+impl std::ops::Deref for Line {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.text
+    }
+}
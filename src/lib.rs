@@ -0,0 +1,57 @@
+//! Solutions to the [Advent of Code 2020](https://adventofcode.com/2020) puzzles.
+//!
+//! Each day's solution lives in its own module and implements [`solution::Solution`].  They're all
+//! listed in [`DAYS`], which is how the runner, the tests and the benchmarks find them.  The
+//! building blocks used by the solutions (e.g. [`util::find_pair`],
+//! [`day13::extended_euclidean_algorithm`] or [`day19::Grammar`]) are public too, for reuse
+//! elsewhere.
+
+pub mod error;
+pub mod fetch;
+pub mod input;
+pub mod memory;
+pub mod runner;
+pub mod solution;
+pub mod util;
+pub mod verify;
+
+/// Declares each day's module and registers its solution, so adding a day is a one-line change
+macro_rules! days {
+    ($($module:ident::$solution:ident),* $(,)?) => {
+        $(pub mod $module;)*
+
+        /// Every implemented day's solution, in day order
+        pub const DAYS: &[&dyn solution::DynSolution] = &[$(&$module::$solution),*];
+    };
+}
+
+days! {
+    day1::Day1,
+    day2::Day2,
+    day3::Day3,
+    day4::Day4,
+    day5::Day5,
+    day6::Day6,
+    day7::Day7,
+    day8::Day8,
+    day9::Day9,
+    day10::Day10,
+    day11::Day11,
+    day12::Day12,
+    day13::Day13,
+    day14::Day14,
+    day15::Day15,
+    day16::Day16,
+    day17::Day17,
+    day18::Day18,
+    day19::Day19,
+    day20::Day20,
+    day21::Day21,
+    day22::Day22,
+    day23::Day23,
+}
+
+/// Look up the solution for the given day, if that day has been implemented
+pub fn solution(day: usize) -> Option<&'static dyn solution::DynSolution> {
+    DAYS.iter().copied().find(|solution| solution.day() == day)
+}
@@ -1,60 +1,383 @@
-mod util;
-
-mod day1;
-mod day2;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-//mod day15;
-mod day16;
-mod day17;
-mod day18;
+mod report;
+mod scaffold;
+mod watch;
+
+use aoc2020::input::{self, InputProvider};
+use aoc2020::{fetch, memory, runner, solution, verify, DAYS};
+use clap::Parser as _;
+
+// Only counts anything once `--memory` turns counting on
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+/// A selection of days to run, parsed from `all`, `N`, `A..B` or `A..=B`
+#[derive(Debug, Clone)]
+struct Days(Vec<usize>);
+
+impl std::str::FromStr for Days {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        fn parse_day(s: &str) -> Result<usize, String> {
+            s.trim()
+                .parse()
+                .map_err(|_| format!("'{}' is not a day number", s))
+        }
+
+        let days = if s == "all" {
+            return Ok(Days(DAYS.iter().map(|solution| solution.day()).collect()));
+        } else if let Some((low, high)) = s.split_once("..=") {
+            parse_day(low)?..=parse_day(high)?
+        } else if let Some((low, high)) = s.split_once("..") {
+            parse_day(low)?..=parse_day(high)?.saturating_sub(1)
+        } else {
+            let day = parse_day(s)?;
+            day..=day
+        };
+
+        if days.is_empty() {
+            return Err(format!("'{}' doesn't select any days", s));
+        }
+        let missing = days.clone().filter(|day| solution(*day).is_none()).collect::<Vec<_>>();
+        if !missing.is_empty() {
+            return Err(format!("no solution implemented for day(s) {:?}", missing));
+        }
+        Ok(Days(days.collect()))
+    }
+}
+
+/// How to download puzzle inputs that aren't in the data directory yet
+#[derive(clap::Args)]
+struct FetchArgs {
+    /// The session cookie to download puzzle inputs with (`run` and `verify` only read the data
+    /// directory if it's unset)
+    #[arg(long, env = "AOC_SESSION", hide_env_values = true)]
+    session: Option<String>,
+    /// The Advent of Code site to download puzzle inputs from
+    #[arg(long, default_value = fetch::Http::DEFAULT_BASE_URL)]
+    base_url: String,
+}
+
+impl FetchArgs {
+    /// How to download puzzle inputs, if there's a session cookie
+    fn fetcher(self) -> Option<fetch::Http> {
+        let base_url = self.base_url;
+        self.session.map(|session| fetch::Http { base_url, session })
+    }
+
+    /// Read inputs from the data directory, downloading missing ones if there's a session cookie
+    fn provider(self, data_dir: std::path::PathBuf) -> Box<dyn InputProvider> {
+        match self.fetcher() {
+            Some(fetcher) => Box::new(fetch::Cached {
+                dir: data_dir,
+                fetcher,
+            }),
+            None => Box::new(input::DataDir(data_dir)),
+        }
+    }
+}
+
+/// Advent of Code 2020
+#[derive(clap::Parser)]
+enum Command {
+    /// Run the solutions for the selected days
+    Run {
+        /// The days to run: a single day (`11`), a range (`7..=12` or `7..13`) or `all`
+        days: Days,
+        /// Only run the given part of each day
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Read the puzzle input from this file (or `-` for standard input) rather than the data
+        /// directory
+        #[arg(long, conflicts_with = "data_dir")]
+        input: Option<std::path::PathBuf>,
+        /// The directory holding each day's puzzle input, as `day{N}.data`
+        #[arg(long, default_value = "data")]
+        data_dir: std::path::PathBuf,
+        #[command(flatten)]
+        fetch: FetchArgs,
+        /// Repeat each step this many times, reporting the minimum and median timings
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        repeat: u32,
+        /// Also report how much memory each step allocates (which slows everything down a little)
+        #[arg(long)]
+        memory: bool,
+        /// Run up to this many days at once (timings will be noisier)
+        #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+        /// How to report the results
+        #[arg(long, value_enum, default_value_t = report::Format::Text)]
+        format: report::Format,
+    },
+    /// Run the solutions for the selected days and check them against the known answers
+    Verify {
+        /// The days to verify, as for `run`
+        #[arg(default_value = "all")]
+        days: Days,
+        /// The file recording the known answers
+        #[arg(long, default_value = "answers.toml")]
+        answers: std::path::PathBuf,
+        /// The directory holding each day's puzzle input, as `day{N}.data`
+        #[arg(long, default_value = "data")]
+        data_dir: std::path::PathBuf,
+        #[command(flatten)]
+        fetch: FetchArgs,
+        /// Verify up to this many days at once
+        #[arg(long, short, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: u32,
+        /// How to report the results
+        #[arg(long, value_enum, default_value_t = report::Format::Text)]
+        format: report::Format,
+    },
+    /// Run one day against every puzzle input in a directory, to check the solution works for
+    /// everyone's input
+    Compare {
+        /// The day to run
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The directory of puzzle inputs, each a `.data` file (by default, `data/day{N}/`)
+        dir: Option<std::path::PathBuf>,
+        /// Only run the given part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+    /// Download the puzzle inputs for the selected days into the data directory, skipping any
+    /// that are already there
+    Fetch {
+        /// The days to download, as for `run`
+        #[arg(default_value = "all")]
+        days: Days,
+        /// The directory to save each day's puzzle input in, as `day{N}.data`
+        #[arg(long, default_value = "data")]
+        data_dir: std::path::PathBuf,
+        #[command(flatten)]
+        fetch: FetchArgs,
+    },
+    /// Start a new day: create its module (with a test stub) and input file, and register it
+    NewDay {
+        /// The day to create
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The root of the repository
+        #[arg(long, default_value = ".")]
+        root: std::path::PathBuf,
+    },
+    /// Re-run a day and its tests whenever its source or puzzle input changes
+    Watch {
+        /// The day to watch
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// The root of the repository
+        #[arg(long, default_value = ".")]
+        root: std::path::PathBuf,
+    },
+}
+
+/// Which parts to run, given an optional `--part` flag
+fn selected_parts(part: Option<u8>) -> Vec<u8> {
+    match part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    }
+}
 
 fn main() {
-    day1::part1();
-    day1::part2();
-    day2::part1();
-    day2::part2();
-    day3::part1();
-    day3::part2();
-    day4::part1();
-    day4::part2();
-    day5::part1();
-    day5::part2();
-    day6::part1();
-    day6::part2();
-    day7::part1();
-    day7::part2();
-    day8::part1();
-    day8::part2();
-    day9::part1();
-    day9::part2();
-    day10::part1();
-    day10::part2();
-    day11::part1();
-    day11::part2();
-    day12::part1();
-    day12::part2();
-    day13::part1();
-    day13::part2();
-    day14::part1();
-    day14::part2();
-    //day15::part1();
-    //day15::part2();
-    day16::part1();
-    day16::part2();
-    day17::part1();
-    day17::part2();
-    day18::part1();
-    day18::part2();
-    println!("Hello, world!");
+    match Command::parse() {
+        Command::Run {
+            days,
+            part,
+            input,
+            data_dir,
+            fetch,
+            repeat,
+            memory,
+            jobs,
+            format,
+        } => {
+            let repeat = repeat as usize;
+            if memory {
+                memory::enable();
+            }
+            // Days without an input in the data directory are skipped, but not an explicit input
+            let skip_missing = input.is_none();
+            let provider: Box<dyn InputProvider> = match input {
+                Some(_) if days.0.len() > 1 => {
+                    <Command as clap::CommandFactory>::command()
+                        .error(
+                            clap::error::ErrorKind::ArgumentConflict,
+                            "--input can only be used when running a single day",
+                        )
+                        .exit();
+                }
+                Some(path) if path.as_os_str() == "-" => Box::new(input::Stdin),
+                Some(path) => Box::new(input::File(path)),
+                None => fetch.provider(data_dir),
+            };
+            let parts = selected_parts(part);
+            let outcomes = runner::run_days(
+                &days.0,
+                &*provider,
+                &parts,
+                repeat,
+                jobs as usize,
+                |day, outcome| {
+                    if format == report::Format::Text {
+                        report::print_outcome(day, outcome);
+                    }
+                },
+            );
+            match format {
+                report::Format::Text => {
+                    let successes = outcomes
+                        .iter()
+                        .filter_map(|(_day, outcome)| outcome.as_ref().ok())
+                        .collect::<Vec<_>>();
+                    report::print_summary(&successes, repeat);
+                }
+                report::Format::Json => report::print_json(&report::records(&outcomes, &parts, None)),
+            }
+            let failed = outcomes.iter().any(|(_day, outcome)| match outcome {
+                Ok(outcome) => outcome.parts.iter().any(|part| part.answer.is_err()),
+                Err(err) => !(skip_missing && err.is_missing_input()),
+            });
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::Verify {
+            days,
+            answers,
+            data_dir,
+            fetch,
+            jobs,
+            format,
+        } => {
+            let expected = verify::ExpectedAnswers::load(&answers).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                std::process::exit(2);
+            });
+            let provider = fetch.provider(data_dir);
+            let outcomes = runner::run_days(
+                &days.0,
+                &*provider,
+                &[1, 2],
+                1,
+                jobs as usize,
+                |day, outcome| {
+                    if format == report::Format::Text {
+                        report::print_verification(day, outcome, &expected);
+                    }
+                },
+            );
+            if format == report::Format::Json {
+                report::print_json(&report::records(&outcomes, &[1, 2], Some(&expected)));
+            }
+            let failed = outcomes.iter().any(|(day, outcome)| match outcome {
+                Ok(outcome) => outcome
+                    .parts
+                    .iter()
+                    .any(|part| match &part.answer {
+                        Ok(answer) => expected.check(*day, part.part, answer).is_failure(),
+                        Err(_) => true,
+                    }),
+                // Like a missing answer, a missing input is reported but isn't a failure
+                Err(err) => !err.is_missing_input(),
+            });
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::Compare { day, dir, part } => {
+            let day = day as usize;
+            let solution = solution(day).unwrap_or_else(|| {
+                eprintln!("No solution implemented for day {}", day);
+                std::process::exit(2);
+            });
+            let dir = dir.unwrap_or_else(|| format!("data/day{}", day).into());
+            let mut inputs = std::fs::read_dir(&dir)
+                .and_then(|entries| {
+                    entries
+                        .map(|entry| entry.map(|entry| entry.path()))
+                        .collect::<std::io::Result<Vec<_>>>()
+                })
+                .unwrap_or_else(|err| {
+                    eprintln!("{}: {}", dir.display(), err);
+                    std::process::exit(2);
+                });
+            inputs.retain(|path| path.is_file() && path.extension() == Some("data".as_ref()));
+            inputs.sort();
+            if inputs.is_empty() {
+                eprintln!("{}: no puzzle inputs found", dir.display());
+                std::process::exit(2);
+            }
+
+            let parts = selected_parts(part);
+            // Panics are reported in the table instead
+            std::panic::set_hook(Box::new(|_| {}));
+            let outcomes = inputs
+                .into_iter()
+                .map(|path| {
+                    let name = path.file_name().map_or_else(
+                        || path.display().to_string(),
+                        |name| name.to_string_lossy().into_owned(),
+                    );
+                    // A solution that isn't general enough may well panic, report that like any
+                    // other error (just for that part, if it's solving that panicked) rather than
+                    // giving up on the remaining inputs
+                    let outcome =
+                        runner::run_day_catching_panics(solution, &input::File(path), &parts, 1)
+                            .map_err(|err| err.to_string());
+                    (name, outcome)
+                })
+                .collect::<Vec<_>>();
+            report::print_comparison(&outcomes, &parts);
+            let failed = outcomes.iter().any(|(_name, outcome)| match outcome {
+                Ok(outcome) => outcome.parts.iter().any(|part| part.answer.is_err()),
+                Err(_) => true,
+            });
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::Fetch {
+            days,
+            data_dir,
+            fetch,
+        } => {
+            let fetcher = fetch.fetcher().unwrap_or_else(|| {
+                <Command as clap::CommandFactory>::command()
+                    .error(
+                        clap::error::ErrorKind::MissingRequiredArgument,
+                        "fetching needs a session cookie, from --session or AOC_SESSION",
+                    )
+                    .exit();
+            });
+            let provider = fetch::Cached {
+                dir: data_dir,
+                fetcher,
+            };
+            let mut failed = false;
+            for day in days.0 {
+                if let Err(err) = provider.lines(day) {
+                    eprintln!("Day {}: {}", day, err);
+                    failed = true;
+                }
+            }
+            if failed {
+                std::process::exit(1);
+            }
+        }
+        Command::NewDay { day, root } => {
+            if let Err(err) = scaffold::new_day(&root, day as usize) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+        Command::Watch { day, root } => {
+            if let Err(err) = watch::watch(&root, day as usize) {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            }
+        }
+    }
 }
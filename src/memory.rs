@@ -0,0 +1,125 @@
+//! Counting how much memory the solutions allocate.
+//!
+//! Install [`CountingAllocator`] as the global allocator and call [`enable`], then wrap the code
+//! of interest in [`measure`].  Counts are kept per thread, so days run in parallel don't muddle
+//! each other's figures.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Whether allocations are being counted at all
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+struct Counters {
+    /// Bytes currently allocated by this thread (negative if it's freed memory allocated elsewhere)
+    current: Cell<isize>,
+    peak: Cell<isize>,
+    allocations: Cell<usize>,
+}
+
+thread_local! {
+    // Const-initialised, so using it from inside the allocator doesn't itself allocate
+    static COUNTERS: Counters = const {
+        Counters {
+            current: Cell::new(0),
+            peak: Cell::new(0),
+            allocations: Cell::new(0),
+        }
+    };
+}
+
+fn record(allocated: usize, freed: usize, allocations: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // Ignore allocations made while the thread is being torn down
+    let _ = COUNTERS.try_with(|counters| {
+        let current = counters.current.get() + allocated as isize - freed as isize;
+        counters.current.set(current);
+        counters.peak.set(counters.peak.get().max(current));
+        counters.allocations.set(counters.allocations.get() + allocations);
+    });
+}
+
+/// A global allocator that forwards to the system allocator, counting what it's asked for
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0, 1);
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record(layout.size(), 0, 1);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        record(0, layout.size(), 0);
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size, layout.size(), 1);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// Start counting allocations (which only works if `CountingAllocator` is the global allocator)
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// How much memory some code allocated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct AllocationStats {
+    /// The most memory in use at once, over and above what was in use beforehand
+    pub peak_bytes: usize,
+    /// How many times memory was allocated (or reallocated)
+    pub allocations: usize,
+}
+
+/// Run `f`, counting the memory it allocates on this thread (if counting is enabled)
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocationStats>) {
+    if !is_enabled() {
+        return (f(), None);
+    }
+    let (start, outer_peak, start_allocations) = COUNTERS.with(|counters| {
+        let start = counters.current.get();
+        let outer_peak = counters.peak.replace(start);
+        (start, outer_peak, counters.allocations.get())
+    });
+    let result = f();
+    let stats = COUNTERS.with(|counters| {
+        let peak = counters.peak.get();
+        counters.peak.set(outer_peak.max(peak));
+        AllocationStats {
+            peak_bytes: (peak - start) as usize,
+            allocations: counters.allocations.get() - start_allocations,
+        }
+    });
+    (result, Some(stats))
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_measure() {
+    enable();
+    let (_, stats) = measure(|| {
+        let small = vec![0u8; 100];
+        drop(small);
+        vec![0u8; 1000]
+    });
+    let stats = stats.unwrap();
+    assert_eq!(stats.allocations, 2);
+    assert_eq!(stats.peak_bytes, 1000);
+}
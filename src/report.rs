@@ -0,0 +1,325 @@
+use std::time::Duration;
+
+use aoc2020::error::Result;
+use aoc2020::memory::AllocationStats;
+use aoc2020::runner::{DayOutcome, Timing};
+use aoc2020::solution::Answer;
+use aoc2020::verify::{ExpectedAnswers, Status};
+
+/// How the runner should report its results
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Format {
+    /// `Day N, Part M: X` lines followed by a table of timings
+    Text,
+    /// A JSON array with one record per day and part
+    Json,
+}
+
+fn print_answer(day: usize, part: u8, answer: &Answer) {
+    if let Answer::Lines(lines) = answer {
+        println!("Day {}, Part {}:", day, part);
+        for line in lines {
+            println!("    {}", line);
+        }
+    } else {
+        println!("Day {}, Part {}: {}", day, part, answer);
+    }
+}
+
+/// Print the answers for one day, or the reason there aren't any
+pub fn print_outcome(day: usize, outcome: &Result<DayOutcome>) {
+    match outcome {
+        Ok(outcome) => {
+            for part in &outcome.parts {
+                match &part.answer {
+                    Ok(answer) => print_answer(day, part.part, answer),
+                    Err(err) => eprintln!("Day {}, Part {}: {}", day, part.part, err),
+                }
+            }
+        }
+        Err(err) if err.is_missing_input() => eprintln!("Day {}: MISSING input ({})", day, err),
+        Err(err) => eprintln!("Day {}: {}", day, err),
+    }
+}
+
+/// Print how each of one day's answers compared to the known answers
+pub fn print_verification(day: usize, outcome: &Result<DayOutcome>, expected: &ExpectedAnswers) {
+    match outcome {
+        Ok(outcome) => {
+            for part in &outcome.parts {
+                let answer = match &part.answer {
+                    Ok(answer) => answer,
+                    Err(err) => {
+                        println!("Day {}, Part {}: ERROR ({})", day, part.part, err);
+                        continue;
+                    }
+                };
+                let status = expected.check(day, part.part, answer);
+                match &status {
+                    Status::Fail(expected) => println!(
+                        "Day {}, Part {}: {} (expected {}, got {})",
+                        day, part.part, status, expected, answer
+                    ),
+                    _ => println!("Day {}, Part {}: {} ({})", day, part.part, status, answer),
+                }
+            }
+        }
+        Err(err) if err.is_missing_input() => println!("Day {}: MISSING input ({})", day, err),
+        Err(err) => println!("Day {}: ERROR ({})", day, err),
+    }
+}
+
+fn format_timing(timing: Timing, repeat: usize) -> String {
+    if repeat > 1 {
+        format!("{:.1?} / {:.1?}", timing.min, timing.median)
+    } else {
+        format!("{:.1?}", timing.min)
+    }
+}
+
+/// Print a table of how long each day took to parse and solve, if any of them could be run
+pub fn print_summary(outcomes: &[&DayOutcome], repeat: usize) {
+    if outcomes.is_empty() {
+        return;
+    }
+    let mut header = vec!["Day".to_string(), "Parse".to_string()];
+    let mut parts = outcomes
+        .iter()
+        .flat_map(|outcome| outcome.parts.iter().map(|part| part.part))
+        .collect::<Vec<_>>();
+    parts.sort_unstable();
+    parts.dedup();
+    header.extend(parts.iter().map(|part| format!("Part {}", part)));
+    header.push("Total".to_string());
+
+    let mut rows = vec![header];
+    let mut grand_total = Timing {
+        min: Duration::default(),
+        median: Duration::default(),
+    };
+    for outcome in outcomes {
+        let mut total = outcome.parse_time;
+        let mut row = vec![
+            outcome.day.to_string(),
+            format_timing(outcome.parse_time, repeat),
+        ];
+        for part in &parts {
+            match outcome.parts.iter().find(|outcome| outcome.part == *part) {
+                Some(outcome) => {
+                    total.min += outcome.time.min;
+                    total.median += outcome.time.median;
+                    row.push(format_timing(outcome.time, repeat));
+                }
+                None => row.push("-".to_string()),
+            }
+        }
+        row.push(format_timing(total, repeat));
+        grand_total.min += total.min;
+        grand_total.median += total.median;
+        rows.push(row);
+    }
+    let mut footer = vec!["All".to_string()];
+    footer.resize(parts.len() + 2, String::new());
+    footer.push(format_timing(grand_total, repeat));
+    rows.push(footer);
+
+    println!();
+    if repeat > 1 {
+        println!("Timings over {} runs (min / median):", repeat);
+    }
+    print_table(&rows, true);
+
+    if outcomes.iter().any(|outcome| outcome.parse_memory.is_some()) {
+        let format_memory = |stats: Option<AllocationStats>| {
+            stats.map_or_else(
+                || "-".to_string(),
+                |stats| format!("{} / {}", format_bytes(stats.peak_bytes), stats.allocations),
+            )
+        };
+        let mut header = vec!["Day".to_string(), "Parse".to_string()];
+        header.extend(parts.iter().map(|part| format!("Part {}", part)));
+        let mut rows = vec![header];
+        for outcome in outcomes {
+            let mut row = vec![outcome.day.to_string(), format_memory(outcome.parse_memory)];
+            for part in &parts {
+                let outcome = outcome.parts.iter().find(|outcome| outcome.part == *part);
+                row.push(format_memory(outcome.and_then(|outcome| outcome.memory)));
+            }
+            rows.push(row);
+        }
+        println!();
+        println!("Memory (peak / allocations):");
+        print_table(&rows, false);
+    }
+}
+
+/// Print a table of each input's answers to a day's puzzle, followed by any errors
+pub fn print_comparison(
+    outcomes: &[(String, std::result::Result<DayOutcome, String>)],
+    parts: &[u8],
+) {
+    let mut header = vec!["Input".to_string()];
+    header.extend(parts.iter().map(|part| format!("Part {}", part)));
+    header.push("Time".to_string());
+    let mut rows = vec![header];
+    for (name, outcome) in outcomes {
+        let mut row = vec![name.clone()];
+        match outcome {
+            Ok(outcome) => {
+                let mut total = outcome.parse_time.min;
+                for part in &outcome.parts {
+                    total += part.time.min;
+                    row.push(match &part.answer {
+                        // Keep multi-line answers on one row of the table
+                        Ok(Answer::Lines(lines)) => lines.join(" / "),
+                        Ok(answer) => answer.to_string(),
+                        Err(_) => "ERROR".to_string(),
+                    });
+                }
+                row.push(format!("{:.1?}", total));
+            }
+            Err(_) => row.resize(parts.len() + 2, "ERROR".to_string()),
+        }
+        rows.push(row);
+    }
+    println!();
+    print_table(&rows, false);
+    for (name, outcome) in outcomes {
+        match outcome {
+            Ok(outcome) => {
+                for part in &outcome.parts {
+                    if let Err(err) = &part.answer {
+                        eprintln!("{}, Part {}: {}", name, part.part, err);
+                    }
+                }
+            }
+            Err(err) => eprintln!("{}: {}", name, err),
+        }
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: &[&str] = &["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// Print rows of cells as a right-aligned table, with a rule under the header (and above the
+/// footer, if there is one)
+fn print_table(rows: &[Vec<String>], footer: bool) {
+    let widths = (0..rows[0].len())
+        .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+        .collect::<Vec<_>>();
+    for (idx, row) in rows.iter().enumerate() {
+        let cells = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:>width$}", cell, width = width))
+            .collect::<Vec<_>>();
+        println!("{}", cells.join(" | "));
+        if idx == 0 || (footer && idx == rows.len() - 2) {
+            let rule = widths.iter().map(|width| "-".repeat(*width)).collect::<Vec<_>>();
+            println!("{}", rule.join("-+-"));
+        }
+    }
+}
+
+/// The machine-readable result of one part of one day
+#[derive(Debug, serde::Serialize)]
+pub struct Record {
+    pub day: usize,
+    pub part: u8,
+    pub answer: Option<Answer>,
+    pub parse_ns: Option<u128>,
+    pub solve_ns: Option<u128>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_memory: Option<AllocationStats>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub solve_memory: Option<AllocationStats>,
+    /// `OK`, `ERROR` or `MISSING` (if there's no puzzle input) when running, or `PASS`, `FAIL`,
+    /// `MISSING` (if there's no puzzle input or known answer) or `ERROR` when verifying
+    pub status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expected: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Flatten the runner's outcomes into one record per day and part, checking the answers if the
+/// expected answers are provided
+pub fn records(
+    outcomes: &[(usize, Result<DayOutcome>)],
+    parts: &[u8],
+    expected: Option<&ExpectedAnswers>,
+) -> Vec<Record> {
+    let mut records = Vec::new();
+    for (day, outcome) in outcomes {
+        match outcome {
+            Ok(outcome) => records.extend(outcome.parts.iter().map(|part| {
+                let record = Record {
+                    day: *day,
+                    part: part.part,
+                    answer: None,
+                    parse_ns: Some(outcome.parse_time.min.as_nanos()),
+                    solve_ns: Some(part.time.min.as_nanos()),
+                    parse_memory: outcome.parse_memory,
+                    solve_memory: part.memory,
+                    status: "ERROR".to_string(),
+                    expected: None,
+                    error: None,
+                };
+                match &part.answer {
+                    Ok(answer) => {
+                        let status = expected.map(|expected| expected.check(*day, part.part, answer));
+                        Record {
+                            answer: Some(answer.clone()),
+                            status: status.as_ref().map_or_else(|| "OK".to_string(), Status::to_string),
+                            expected: match status {
+                                Some(Status::Fail(expected)) => Some(expected),
+                                _ => None,
+                            },
+                            ..record
+                        }
+                    }
+                    Err(err) => Record {
+                        error: Some(err.to_string()),
+                        ..record
+                    },
+                }
+            })),
+            Err(err) => records.extend(parts.iter().map(|part| Record {
+                day: *day,
+                part: *part,
+                answer: None,
+                parse_ns: None,
+                solve_ns: None,
+                parse_memory: None,
+                solve_memory: None,
+                status: if err.is_missing_input() {
+                    "MISSING".to_string()
+                } else {
+                    "ERROR".to_string()
+                },
+                expected: None,
+                error: Some(err.to_string()),
+            })),
+        }
+    }
+    records
+}
+
+pub fn print_json(records: &[Record]) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(records).expect("Records are always serializable")
+    );
+}
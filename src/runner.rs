@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+use crate::error::{Error, Result};
+use crate::input::InputProvider;
+use crate::memory::{self, AllocationStats};
+use crate::solution::{Answer, DynSolution};
+
+/// How long a step took, over one or more repetitions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timing {
+    pub min: Duration,
+    pub median: Duration,
+}
+
+impl Timing {
+    fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Timing {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+        }
+    }
+
+    /// Run `f` `repeat` times (at least once), returning the first result and how long it took
+    fn measure<T>(repeat: usize, mut f: impl FnMut() -> T) -> (T, Timing) {
+        let start = Instant::now();
+        let result = f();
+        let mut samples = vec![start.elapsed()];
+        for _ in 1..repeat {
+            let start = Instant::now();
+            let _ = f();
+            samples.push(start.elapsed());
+        }
+        (result, Timing::from_samples(samples))
+    }
+}
+
+/// The answer to one part of a day's puzzle, as produced by the runner
+pub struct PartOutcome {
+    pub part: u8,
+    /// The answer, or `Error::Panic` if the part panicked
+    pub answer: Result<Answer>,
+    pub time: Timing,
+    /// What the first run allocated, if allocations are being counted
+    pub memory: Option<AllocationStats>,
+}
+
+/// The outcome of running one day's solution
+pub struct DayOutcome {
+    pub day: usize,
+    /// How long parsing took (not including reading the input from wherever it's stored)
+    pub parse_time: Timing,
+    /// What parsing allocated (the first time), if allocations are being counted
+    pub parse_memory: Option<AllocationStats>,
+    pub parts: Vec<PartOutcome>,
+}
+
+/// Parse a day's puzzle input, then solve each of the requested parts.
+///
+/// Each step is repeated `repeat` times to get a more reliable timing. A part that panics is
+/// reported in its `PartOutcome`, so the other part still gets an answer.
+pub fn run_day(
+    solution: &dyn DynSolution,
+    provider: &dyn InputProvider,
+    parts: &[u8],
+    repeat: usize,
+) -> Result<DayOutcome> {
+    let buffered = provider.lines(solution.day())?.buffer()?;
+    let mut parse_samples = Vec::new();
+    let mut input = None;
+    let mut parse_memory = None;
+    for _ in 0..repeat.max(1) {
+        let lines = buffered.lines();
+        let start = Instant::now();
+        let (parsed, memory) = memory::measure(|| solution.parse(lines));
+        let parsed = parsed?;
+        parse_samples.push(start.elapsed());
+        input.get_or_insert(parsed);
+        parse_memory = parse_memory.or(memory);
+    }
+    let input = input.expect("Parsed at least once");
+
+    let parts = parts
+        .iter()
+        .map(|part| {
+            let mut memory = None;
+            let (answer, time) = Timing::measure(repeat, || {
+                let (answer, stats) = memory::measure(|| {
+                    catch_panics(|| match part {
+                        1 => Ok(input.part1()),
+                        2 => Ok(input.part2()),
+                        _ => unreachable!("Puzzles only have two parts"),
+                    })
+                });
+                memory = memory.or(stats);
+                answer
+            });
+            PartOutcome {
+                part: *part,
+                answer,
+                time,
+                memory,
+            }
+        })
+        .collect();
+
+    Ok(DayOutcome {
+        day: solution.day(),
+        parse_time: Timing::from_samples(parse_samples),
+        parse_memory,
+        parts,
+    })
+}
+
+/// The message a panic was raised with, if it was a string
+fn panic_message(panic: &(dyn std::any::Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message
+    } else {
+        "unknown cause"
+    }
+}
+
+/// Run `f`, reporting a panic (e.g. because a solution isn't general enough for its input) as an
+/// error rather than letting it take down the caller
+fn catch_panics<T>(f: impl FnOnce() -> Result<T>) -> Result<T> {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_else(|panic| {
+        Err(Error::Panic {
+            message: panic_message(&*panic).to_string(),
+        })
+    })
+}
+
+/// Run a day like `run_day`, but report a panic while parsing as an error too
+pub fn run_day_catching_panics(
+    solution: &dyn DynSolution,
+    provider: &dyn InputProvider,
+    parts: &[u8],
+    repeat: usize,
+) -> Result<DayOutcome> {
+    catch_panics(|| run_day(solution, provider, parts, repeat))
+}
+
+/// Run each of the given days on up to `jobs` threads, calling `on_outcome` for each one in the
+/// order given (as soon as it and all the days before it have finished).
+///
+/// A day that panics is reported as an error, without affecting the others.
+pub fn run_days(
+    days: &[usize],
+    provider: &dyn InputProvider,
+    parts: &[u8],
+    repeat: usize,
+    jobs: usize,
+    mut on_outcome: impl FnMut(usize, &Result<DayOutcome>),
+) -> Vec<(usize, Result<DayOutcome>)> {
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            let sender = sender.clone();
+            let next = &next;
+            scope.spawn(move || {
+                // Each worker claims the next day nobody has started yet, so slow days don't hold
+                // up the rest of the queue
+                while let Some(day) = days.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let solution = crate::solution(*day).expect("Only implemented days can be run");
+                    let outcome = run_day_catching_panics(solution, provider, parts, repeat);
+                    if sender.send((*day, outcome)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut finished = HashMap::new();
+        let mut outcomes = Vec::with_capacity(days.len());
+        for (day, outcome) in receiver {
+            finished.insert(day, outcome);
+            while let Some(outcome) = days.get(outcomes.len()).and_then(|day| finished.remove(day)) {
+                let day = days[outcomes.len()];
+                on_outcome(day, &outcome);
+                outcomes.push((day, outcome));
+            }
+        }
+        outcomes
+    })
+}
@@ -0,0 +1,108 @@
+use std::path::Path;
+
+const TEMPLATE: &str = "use crate::error::Result;
+use crate::input::Lines;
+use crate::solution::{Answer, Solution};
+
+pub struct Day{N};
+
+impl Solution for Day{N} {
+    const DAY: usize = {N};
+
+    type Input = Vec<String>;
+
+    fn parse(lines: Lines) -> Result<Self::Input> {
+        lines.parse_each(|line| Ok(line.to_string()))
+    }
+
+    fn part1(_data: &Self::Input) -> Answer {
+        \"unsolved\".into()
+    }
+
+    fn part2(_data: &Self::Input) -> Answer {
+        \"unsolved\".into()
+    }
+}
+
+#[test]
+#[ignore = \"the example hasn't been filled in yet\"]
+fn test_example() {
+    let data = Day{N}::parse(crate::input::from_str(\"\")).unwrap();
+    assert_eq!(Day{N}::part1(&data), 0);
+    assert_eq!(Day{N}::part2(&data), 0);
+}
+";
+
+/// Add `dayN::DayN` to the `days!` list in `lib.rs`, keeping it in day order
+fn register(lib: &str, day: usize) -> Result<String, String> {
+    let start = lib
+        .find("days! {\n")
+        .ok_or("couldn't find the `days!` list in src/lib.rs")?
+        + "days! {\n".len();
+    let end = start
+        + lib[start..]
+            .find("\n}")
+            .ok_or("couldn't find the end of the `days!` list in src/lib.rs")?
+        + 1;
+
+    let mut entries = lib[start..end]
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let module = line.trim_end_matches(',');
+            let number = module
+                .split("::")
+                .next()
+                .and_then(|module| module.strip_prefix("day"))
+                .and_then(|number| number.parse::<usize>().ok())
+                .ok_or_else(|| format!("unexpected entry '{}' in the `days!` list", line))?;
+            Ok((number, module.to_string()))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if entries.iter().any(|(number, _)| *number == day) {
+        return Err(format!("day {} is already registered in src/lib.rs", day));
+    }
+    entries.push((day, format!("day{0}::Day{0}", day)));
+    entries.sort();
+
+    let list = entries
+        .iter()
+        .map(|(_, module)| format!("    {},\n", module))
+        .collect::<String>();
+    Ok(format!("{}{}{}", &lib[..start], list, &lib[end..]))
+}
+
+/// Create the module and (empty) puzzle input for a new day, and register it in the library
+pub fn new_day(root: &Path, day: usize) -> Result<(), String> {
+    let module = root.join("src").join(format!("day{}.rs", day));
+    if module.exists() {
+        return Err(format!("{} already exists", module.display()));
+    }
+    let lib_path = root.join("src").join("lib.rs");
+    let lib = std::fs::read_to_string(&lib_path)
+        .map_err(|err| format!("{}: {}", lib_path.display(), err))?;
+    let lib = register(&lib, day)?;
+
+    let write = |path: &Path, contents: &str| {
+        std::fs::write(path, contents).map_err(|err| format!("{}: {}", path.display(), err))?;
+        println!("Wrote {}", path.display());
+        Ok::<_, String>(())
+    };
+    write(&module, &TEMPLATE.replace("{N}", &day.to_string()))?;
+    let data = root.join("data").join(format!("day{}.data", day));
+    if !data.exists() {
+        write(&data, "")?;
+    }
+    write(&lib_path, &lib)
+}
+
+#[test]
+fn test_register() {
+    let lib = "days! {\n    day1::Day1,\n    day10::Day10,\n}\n\nfn solution() {}\n";
+    assert_eq!(
+        register(lib, 9).unwrap(),
+        "days! {\n    day1::Day1,\n    day9::Day9,\n    day10::Day10,\n}\n\nfn solution() {}\n"
+    );
+    assert!(register(lib, 10).is_err());
+}
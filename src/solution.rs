@@ -0,0 +1,194 @@
+use crate::error::Result;
+use crate::input::Lines;
+
+/// The answer to one part of a day's puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Integer(i128),
+    Text(String),
+    /// An answer that can only be read by eye (e.g. a picture drawn in the output)
+    Lines(Vec<String>),
+}
+
+impl Answer {
+    /// Whether this answer matches another, treating a multi-line answer as equal to the same
+    /// lines joined into a single string (which is easier to write down)
+    pub fn matches(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Text(text), Answer::Lines(lines))
+            | (Answer::Lines(lines), Answer::Text(text)) => *text == lines.join("\n"),
+            _ => self == other,
+        }
+    }
+}
+
+macro_rules! integer_answer {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for Answer {
+                fn from(value: $ty) -> Self {
+                    use std::convert::TryFrom as _;
+                    Answer::Integer(i128::try_from(value).expect("Answer too large for an i128"))
+                }
+            }
+        )*
+    };
+}
+
+integer_answer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(value: Vec<String>) -> Self {
+        Answer::Lines(value)
+    }
+}
+
+/// Allows tests to write `assert_eq!(Day13::part2(&input), 1068781)`
+impl PartialEq<i128> for Answer {
+    fn eq(&self, other: &i128) -> bool {
+        matches!(self, Answer::Integer(value) if value == other)
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        matches!(self, Answer::Text(value) if value == other)
+    }
+}
+
+/// Answers are written as integers, strings or lists of strings (one per line)
+impl serde::Serialize for Answer {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Answer::Integer(value) => serializer.serialize_i128(*value),
+            Answer::Text(value) => serializer.serialize_str(value),
+            Answer::Lines(lines) => serializer.collect_seq(lines),
+        }
+    }
+}
+
+/// Answers are read from integers, strings or lists of strings (one per line)
+impl<'de> serde::Deserialize<'de> for Answer {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        struct Visitor;
+
+        impl<'de> serde::de::Visitor<'de> for Visitor {
+            type Value = Answer;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("an integer, a string or a list of strings")
+            }
+
+            fn visit_i64<E>(self, value: i64) -> std::result::Result<Answer, E> {
+                Ok(value.into())
+            }
+
+            fn visit_u64<E>(self, value: u64) -> std::result::Result<Answer, E> {
+                Ok(value.into())
+            }
+
+            fn visit_i128<E>(self, value: i128) -> std::result::Result<Answer, E> {
+                Ok(value.into())
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Answer, E> {
+                Ok(value.into())
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> std::result::Result<Answer, A::Error>
+            where
+                A: serde::de::SeqAccess<'de>,
+            {
+                let mut lines = Vec::new();
+                while let Some(line) = seq.next_element()? {
+                    lines.push(line);
+                }
+                Ok(Answer::Lines(lines))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Lines(lines) => write!(f, "{}", lines.join("\n")),
+        }
+    }
+}
+
+/// A solution to one day's puzzle.
+///
+/// The puzzle input is parsed once and then shared between both parts.
+pub trait Solution {
+    /// The day of the advent calendar this solves
+    const DAY: usize;
+
+    /// The parsed form of the puzzle input
+    type Input;
+
+    /// Parse the puzzle input, reporting where any malformed input was found
+    fn parse(lines: Lines) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Answer;
+
+    fn part2(input: &Self::Input) -> Answer;
+}
+
+/// An object-safe view of a `Solution`, so that all the days can be held in a single registry.
+///
+/// This is implemented for every `Solution`, there's no need to implement it by hand.
+pub trait DynSolution: Sync {
+    fn day(&self) -> usize;
+
+    fn parse(&self, lines: Lines) -> Result<Box<dyn ParsedInput>>;
+}
+
+/// A day's puzzle input, parsed and ready to be solved
+pub trait ParsedInput {
+    fn part1(&self) -> Answer;
+
+    fn part2(&self) -> Answer;
+}
+
+struct Parsed<S: Solution>(S::Input);
+
+impl<S: Solution> ParsedInput for Parsed<S> {
+    fn part1(&self) -> Answer {
+        S::part1(&self.0)
+    }
+
+    fn part2(&self) -> Answer {
+        S::part2(&self.0)
+    }
+}
+
+impl<S> DynSolution for S
+where
+    S: Solution + Sync + 'static,
+    S::Input: 'static,
+{
+    fn day(&self) -> usize {
+        S::DAY
+    }
+
+    fn parse(&self, lines: Lines) -> Result<Box<dyn ParsedInput>> {
+        Ok(Box::new(Parsed::<S>(S::parse(lines)?)))
+    }
+}
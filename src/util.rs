@@ -1,22 +1,13 @@
-pub fn data_lines(day: usize) -> impl Iterator<Item = String> {
-    use std::io::BufRead as _;
-    let filename = format!("data/day{}.data", day);
-    let file = std::fs::File::open(filename).unwrap();
-    let file = std::io::BufReader::new(file);
-    file.lines().map(Result::unwrap)
-}
+pub mod grid;
+pub mod parse;
 
-pub trait StrExt {
-    fn split_once(&self, substring: &str) -> (&Self, &Self);
-}
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
 
-impl StrExt for str {
-    fn split_once(&self, substring: &str) -> (&Self, &Self) {
-        let idx = self.find(substring).unwrap();
-        (&self[..idx], &self[idx + substring.len()..])
-    }
-}
+use crate::error::{Error, Result};
+use crate::input::{Line, Lines};
 
+/// Find two values at different positions in `data` that sum to `target`, smallest first
 pub fn find_pair<T: Copy + Ord + PartialEq + std::ops::Add<T, Output = T>>(
     data: &[T],
     target: T,
@@ -24,7 +15,7 @@ pub fn find_pair<T: Copy + Ord + PartialEq + std::ops::Add<T, Output = T>>(
     let mut data = data.to_vec();
     data.sort_unstable();
     let mut low = 0;
-    let mut high = data.len() - 1;
+    let mut high = data.len().saturating_sub(1);
     while low < high {
         if data[low] + data[high] == target {
             return Some((data[low], data[high]));
@@ -36,3 +27,89 @@ pub fn find_pair<T: Copy + Ord + PartialEq + std::ops::Add<T, Output = T>>(
     }
     None
 }
+
+/// Pick a different value for each key from its candidates, by repeatedly settling a key that has
+/// only one candidate left and ruling that value out for every other key.
+///
+/// Returns `None` if that gets stuck before every key is settled, i.e. there's no solution or
+/// finding it would need guesswork.
+pub fn assign_unique<K: Clone + Eq + Hash, V: Eq + Hash>(
+    mut candidates: HashMap<K, HashSet<V>>,
+) -> Option<HashMap<K, V>> {
+    let mut assigned = HashMap::with_capacity(candidates.len());
+    while !candidates.is_empty() {
+        let key = candidates
+            .iter()
+            .find(|(_, values)| values.len() == 1)
+            .map(|(key, _)| key.clone())?;
+        let value = candidates.remove(&key)?.into_iter().next()?;
+        for values in candidates.values_mut() {
+            values.remove(&value);
+        }
+        assigned.insert(key, value);
+    }
+    Some(assigned)
+}
+
+/// Iterate over the groups of lines separated by blank lines, skipping empty groups (so leading,
+/// trailing and repeated blank lines are harmless)
+pub fn records(lines: Lines) -> Records {
+    Records { lines }
+}
+
+/// The iterator returned by `records`
+pub struct Records {
+    lines: Lines,
+}
+
+impl Records {
+    /// Delegates to `Lines::error`
+    pub fn error(&self, message: impl std::fmt::Display) -> Error {
+        self.lines.error(message)
+    }
+}
+
+impl Iterator for Records {
+    type Item = Result<Vec<Line>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut record = Vec::new();
+        for line in &mut self.lines {
+            let line = match line {
+                Ok(line) => line,
+                Err(err) => return Some(Err(err)),
+            };
+            if !line.trim().is_empty() {
+                record.push(line);
+            } else if !record.is_empty() {
+                break;
+            }
+        }
+        if record.is_empty() {
+            None
+        } else {
+            Some(Ok(record))
+        }
+    }
+}
+
+#[test]
+fn test_records() {
+    let records = records(crate::input::from_str("\na\nb\n\n\n \nc\n\n"))
+        .map(|record| record.unwrap().iter().map(|line| line.to_string()).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(records, [vec!["a", "b"], vec!["c"]]);
+}
+
+#[test]
+fn test_assign_unique() {
+    let candidates = |pairs: &[(char, &[u8])]| {
+        pairs
+            .iter()
+            .map(|(key, values)| (*key, values.iter().copied().collect::<HashSet<_>>()))
+            .collect::<HashMap<_, _>>()
+    };
+    let assigned = assign_unique(candidates(&[('a', &[1, 2, 3]), ('b', &[2]), ('c', &[2, 3])]));
+    assert_eq!(assigned, Some([('a', 1), ('b', 2), ('c', 3)].iter().copied().collect()));
+    assert_eq!(assign_unique(candidates(&[('a', &[1, 2]), ('b', &[1, 2])])), None);
+}
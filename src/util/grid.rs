@@ -0,0 +1,336 @@
+//! A rectangular grid of cells, as used by the puzzles drawn as pictures of characters.
+//!
+//! Positions are `(x, y)` pairs, with `(0, 0)` the top-left cell and `y` increasing downwards.
+
+use std::ops::{Index, IndexMut};
+
+use crate::error::Result;
+use crate::input::Line;
+
+/// An `(x, y)` position in a grid
+pub type Position = (usize, usize);
+
+/// The four directions to the cells sharing an edge with a cell, as `(dx, dy)` offsets
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The eight directions to the cells sharing an edge or a corner with a cell
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// The cells, row by row
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Build a grid from its rows, which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "Grid rows must all be the same length"
+        );
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Build a grid by calling `f` for each position, row by row
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Position) -> T) -> Self {
+        Grid {
+            width,
+            height,
+            cells: (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(&mut f)
+                .collect(),
+        }
+    }
+
+    /// Parse a picture of the grid, one row per line, converting each character to a cell with
+    /// `parse_cell` (which returns `None` for characters that can't appear in the grid).
+    ///
+    /// This takes the rest of a puzzle input (i.e. `Lines`), or any other source of lines.
+    pub fn parse(
+        lines: impl IntoIterator<Item = Result<Line>>,
+        mut parse_cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self> {
+        let mut width = None;
+        let mut cells = Vec::new();
+        let mut height = 0;
+        for line in lines {
+            let line = line?;
+            let row_start = cells.len();
+            for (idx, c) in line.char_indices() {
+                match parse_cell(c) {
+                    Some(cell) => cells.push(cell),
+                    None => return Err(line.error_at(idx, format!("unexpected character '{}'", c))),
+                }
+            }
+            let row_width = cells.len() - row_start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(line.error(format!(
+                        "expected a row of {} cells, found {}",
+                        width, row_width
+                    )))
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, (x, y): Position) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (x, y): Position) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    /// Look up a cell in the grid as if it were tiled infinitely in every direction
+    pub fn get_wrapping(&self, x: isize, y: isize) -> &T {
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        &self[(x, y)]
+    }
+
+    /// Move from a position by `(dx, dy)`, if that's still inside the grid
+    pub fn offset(&self, (x, y): Position, (dx, dy): (isize, isize)) -> Option<Position> {
+        let x = x.checked_add_signed(dx)?;
+        let y = y.checked_add_signed(dy)?;
+        if x < self.width && y < self.height {
+            Some((x, y))
+        } else {
+            None
+        }
+    }
+
+    /// The positions of the (up to four) cells sharing an edge with the given cell
+    pub fn neighbours4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ORTHOGONAL
+            .iter()
+            .filter_map(move |direction| self.offset(position, *direction))
+    }
+
+    /// The positions of the (up to eight) cells sharing an edge or corner with the given cell
+    pub fn neighbours8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        ALL_DIRECTIONS
+            .iter()
+            .filter_map(move |direction| self.offset(position, *direction))
+    }
+
+    /// The positions visited by repeatedly stepping in `direction` from `start` (not including
+    /// `start` itself) until the edge of the grid
+    pub fn ray(
+        &self,
+        start: Position,
+        direction: (isize, isize),
+    ) -> impl Iterator<Item = Position> + '_ {
+        std::iter::successors(self.offset(start, direction), move |position| {
+            self.offset(*position, direction)
+        })
+    }
+
+    /// Every position in the grid, row by row
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Every cell in the grid with its position, row by row
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Each row of the grid, from top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on zero, but there are no rows to return if the grid has no width
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells in one row, from left to right
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// The cells in one column, from top to bottom
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "Column {} is outside the grid", x);
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Build a grid of the same shape by converting each cell
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draw the grid, with one character per cell
+    pub fn render(&self, mut f: impl FnMut(&T) -> char) -> String {
+        let mut picture = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            picture.extend(row.iter().map(&mut f));
+            picture.push('\n');
+        }
+        picture
+    }
+}
+
+/// Rearranging a grid's cells, e.g. to try a picture in every orientation
+impl<T: Clone> Grid<T> {
+    /// Swap rows and columns (i.e. reflect in the leading diagonal)
+    pub fn transpose(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| self[(y, x)].clone())
+    }
+
+    /// Rotate a quarter-turn clockwise
+    pub fn rotate_clockwise(&self) -> Self {
+        Grid::from_fn(self.height, self.width, |(x, y)| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Mirror left-to-right
+    pub fn flip_horizontal(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(self.width - 1 - x, y)].clone()
+        })
+    }
+
+    /// Mirror top-to-bottom
+    pub fn flip_vertical(&self) -> Self {
+        Grid::from_fn(self.width, self.height, |(x, y)| {
+            self[(x, self.height - 1 - y)].clone()
+        })
+    }
+
+    /// All eight ways of rotating and flipping the grid: the four rotations (starting with the
+    /// grid as it is), then the four rotations of its mirror image
+    pub fn orientations(&self) -> Vec<Self> {
+        let mut orientations = Vec::with_capacity(8);
+        for start in [self.clone(), self.flip_horizontal()] {
+            let mut grid = start;
+            for _ in 0..4 {
+                let next = grid.rotate_clockwise();
+                orientations.push(grid);
+                grid = next;
+            }
+        }
+        orientations
+    }
+
+    /// Copy out the `width` by `height` rectangle with its top-left corner at `(x, y)`
+    pub fn crop(&self, (x, y): Position, width: usize, height: usize) -> Self {
+        assert!(
+            x + width <= self.width && y + height <= self.height,
+            "Cropped area is outside the grid"
+        );
+        Grid::from_fn(width, height, |(dx, dy)| self[(x + dx, y + dy)].clone())
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("{:?} is outside the grid", position))
+    }
+}
+
+/// Draws the grid row by row, using each cell's own `Display`
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_grid() {
+    let grid = Grid::parse(crate::input::from_str("#..\n.#.\n..#\n.##"), |c| match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None,
+    })
+    .unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 4));
+    assert_eq!(grid.neighbours4((0, 0)).collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    assert_eq!(grid.neighbours8((2, 3)).count(), 3);
+    assert_eq!(grid.ray((0, 0), (1, 1)).collect::<Vec<_>>(), [(1, 1), (2, 2)]);
+    assert!(*grid.get_wrapping(-1, 6));
+    assert_eq!(grid.render(|cell| if *cell { '#' } else { '.' }), "#..\n.#.\n..#\n.##\n");
+    assert_eq!(grid.map(|cell| *cell as u8).to_string(), "100\n010\n001\n011\n");
+
+    assert_eq!(grid.row(3), [false, true, true]);
+    assert_eq!(grid.column(1).filter(|cell| **cell).count(), 2);
+
+    let render = |grid: &Grid<char>| grid.render(|c| *c);
+    let letters = Grid::from_rows(vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]);
+    assert_eq!(render(&letters.rotate_clockwise()), "da\neb\nfc\n");
+    assert_eq!(render(&letters.transpose()), "ad\nbe\ncf\n");
+    assert_eq!(render(&letters.flip_horizontal()), "cba\nfed\n");
+    assert_eq!(render(&letters.flip_vertical()), "def\nabc\n");
+    assert_eq!(render(&letters.crop((1, 0), 2, 2)), "bc\nef\n");
+    let orientations = letters.orientations();
+    assert_eq!(orientations.len(), 8);
+    assert!(orientations.contains(&letters.transpose()));
+    assert!(orientations.contains(&letters.flip_vertical()));
+    assert_eq!(orientations[4], letters.flip_horizontal());
+
+    let err = Grid::parse(crate::input::from_str("#.\n#"), |_| Some(())).err().unwrap();
+    assert_eq!(err.to_string(), "<input>:2: expected a row of 2 cells, found 1");
+}
@@ -0,0 +1,152 @@
+//! Fallible helpers for picking apart lines of puzzle input.
+//!
+//! Each helper works on a [`Field`], which remembers where it is in its line, so that when the
+//! input isn't in the expected format the error can point at the problem.
+//!
+//! Inputs made of blank-line-separated records are split up with [`crate::util::records`].
+
+use std::str::FromStr;
+
+use crate::error::{Error, Result};
+use crate::input::Line;
+
+/// Part (or all) of a line of puzzle input.  Dereferences to the field's text.
+#[derive(Clone, Copy)]
+pub struct Field<'a> {
+    line: &'a Line,
+    /// The byte offset of the field within the line
+    offset: usize,
+    text: &'a str,
+}
+
+impl<'a> Field<'a> {
+    /// The whole of a line
+    pub fn new(line: &'a Line) -> Self {
+        Field {
+            line,
+            offset: 0,
+            text: line,
+        }
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.text
+    }
+
+    /// Build a field from a slice of this field's text
+    fn slice(&self, text: &'a str) -> Field<'a> {
+        Field {
+            line: self.line,
+            offset: self.offset + (text.as_ptr() as usize - self.text.as_ptr() as usize),
+            text,
+        }
+    }
+
+    /// Build an error pointing at the start of this field
+    pub fn error(&self, message: impl std::fmt::Display) -> Error {
+        self.line.error_at(self.offset, message)
+    }
+
+    /// Split around the first occurrence of `separator`, which must be present
+    pub fn split_once(&self, separator: &str) -> Result<(Field<'a>, Field<'a>)> {
+        let idx = self
+            .text
+            .find(separator)
+            .ok_or_else(|| self.error(format!("expected '{}' in '{}'", separator, self.text)))?;
+        Ok((
+            self.slice(&self.text[..idx]),
+            self.slice(&self.text[idx + separator.len()..]),
+        ))
+    }
+
+    /// Split after the first `mid` bytes, which must fall on a character boundary
+    pub fn split_at(&self, mid: usize) -> Result<(Field<'a>, Field<'a>)> {
+        if !self.text.is_char_boundary(mid) {
+            return Err(self.error(format!(
+                "expected at least {} characters in '{}'",
+                mid, self.text
+            )));
+        }
+        let (head, tail) = self.text.split_at(mid);
+        Ok((self.slice(head), self.slice(tail)))
+    }
+
+    /// Split around every occurrence of `separator`
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Field<'a>> + 'a {
+        let field = *self;
+        self.text.split(separator).map(move |text| field.slice(text))
+    }
+
+    /// Remove `prefix`, which must be present
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Field<'a>> {
+        match self.text.strip_prefix(prefix) {
+            Some(rest) => Ok(self.slice(rest)),
+            None => Err(self.error(format!("expected '{}'", prefix))),
+        }
+    }
+
+    /// Remove `suffix`, which must be present
+    pub fn strip_suffix(&self, suffix: &str) -> Result<Field<'a>> {
+        match self.text.strip_suffix(suffix) {
+            Some(rest) => Ok(self.slice(rest)),
+            None => Err(self
+                .slice(&self.text[self.text.len()..])
+                .error(format!("expected '{}'", suffix))),
+        }
+    }
+
+    /// Split a field like `mem[8]` into the part before the brackets and the part inside them
+    pub fn bracketed(&self, open: char, close: char) -> Result<(Field<'a>, Field<'a>)> {
+        let (name, rest) = self.split_once(open.encode_utf8(&mut [0; 4]))?;
+        let inside = rest.strip_suffix(close.encode_utf8(&mut [0; 4]))?;
+        Ok((name, inside))
+    }
+
+    /// Parse the whole field as a single value
+    pub fn parse<T>(&self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.text
+            .parse()
+            .map_err(|err| self.error(format!("invalid value '{}': {}", self.text, err)))
+    }
+
+    /// Parse the whole field as a number with an explicit sign, like `+3` or `-99`
+    pub fn parse_signed<T>(&self) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        if !self.text.starts_with(['+', '-']) {
+            return Err(self.error(format!("expected a sign on '{}'", self.text)));
+        }
+        self.parse()
+    }
+}
+
+impl std::ops::Deref for Field<'_> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.text
+    }
+}
+
+#[test]
+fn test_error_positions() {
+    let lines = crate::input::from_str("mem[8] = 11\nmem[x = 3")
+        .map(Result::unwrap)
+        .collect::<Vec<_>>();
+
+    let (target, value) = Field::new(&lines[0]).split_once(" = ").unwrap();
+    let (name, address) = target.bracketed('[', ']').unwrap();
+    assert_eq!((&*name, address.parse::<u64>().unwrap()), ("mem", 8));
+    let err = value.parse_signed::<i64>().unwrap_err();
+    assert_eq!(err.to_string(), "<input>:1:10: expected a sign on '11'");
+
+    let (target, _) = Field::new(&lines[1]).split_once(" = ").unwrap();
+    let err = target.bracketed('[', ']').err().unwrap();
+    assert_eq!(err.to_string(), "<input>:2:6: expected ']'");
+}
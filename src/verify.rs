@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::solution::Answer;
+
+/// The known-correct answers to each puzzle, as recorded in `answers.toml`:
+///
+/// ```toml
+/// [day1]
+/// part1 = 712075
+/// part2 = 145245270
+/// ```
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(transparent)]
+pub struct ExpectedAnswers(BTreeMap<String, BTreeMap<String, Answer>>);
+
+impl ExpectedAnswers {
+    pub fn load(path: &Path) -> Result<Self> {
+        let name = path.display().to_string();
+        let text = std::fs::read_to_string(path).map_err(|error| Error::Io {
+            name: name.clone(),
+            error,
+        })?;
+        toml::from_str(&text).map_err(|err| {
+            let offset = err.span().map_or(0, |span| span.start);
+            let line_start = text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+            Error::Parse {
+                name,
+                line: text[..offset].matches('\n').count() + 1,
+                column: Some(offset - line_start + 1),
+                message: err.message().to_string(),
+            }
+        })
+    }
+
+    pub fn get(&self, day: usize, part: u8) -> Option<&Answer> {
+        self.0
+            .get(&format!("day{}", day))?
+            .get(&format!("part{}", part))
+    }
+
+    /// Compare an answer against the expected one
+    pub fn check(&self, day: usize, part: u8, answer: &Answer) -> Status {
+        match self.get(day, part) {
+            None => Status::Missing,
+            Some(expected) if expected.matches(answer) => Status::Pass,
+            Some(expected) => Status::Fail(expected.clone()),
+        }
+    }
+}
+
+/// How an answer compared against the expected answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    /// The answer was wrong, this is what was expected
+    Fail(Answer),
+    /// There's no record of the correct answer
+    Missing,
+}
+
+impl Status {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Status::Fail(_))
+    }
+}
+
+impl std::fmt::Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Status::Pass => "PASS",
+            Status::Fail(_) => "FAIL",
+            Status::Missing => "MISSING",
+        })
+    }
+}
+
+#[test]
+fn test_check() {
+    let expected: ExpectedAnswers = toml::from_str(
+        r#"
+        [day1]
+        part1 = 712075
+        [day20]
+        part2 = "ABC"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(expected.check(1, 1, &712075.into()), Status::Pass);
+    assert_eq!(expected.check(1, 1, &5.into()), Status::Fail(712075.into()));
+    assert_eq!(expected.check(1, 2, &5.into()), Status::Missing);
+    assert_eq!(expected.check(20, 2, &"ABC".into()), Status::Pass);
+}
@@ -0,0 +1,54 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// How often to check whether the watched files have changed
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When each file was last modified (or `None` if it doesn't exist)
+fn modified_times(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| std::fs::metadata(file).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+/// Run a cargo command in the repository, reporting (but otherwise ignoring) failures
+fn cargo(root: &Path, args: &[&str]) {
+    let cargo = std::env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    match Command::new(cargo).args(args).current_dir(root).status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => println!("`cargo {}` failed ({})", args.join(" "), status),
+        Err(err) => println!("Couldn't run `cargo {}`: {}", args.join(" "), err),
+    }
+}
+
+/// Re-run the day (and its tests) whenever its source or puzzle input changes.  Never returns
+/// unless the source can't be found.
+pub fn watch(root: &Path, day: usize) -> Result<(), String> {
+    let source = root.join("src").join(format!("day{}.rs", day));
+    if !source.exists() {
+        return Err(format!("{} doesn't exist", source.display()));
+    }
+    let files = [source, root.join("data").join(format!("day{}.data", day))];
+    let day = day.to_string();
+    let test_filter = format!("day{}::", day);
+
+    let mut last_seen = None;
+    loop {
+        let modified = modified_times(&files);
+        if last_seen.as_ref() != Some(&modified) {
+            last_seen = Some(modified);
+            println!("=== Running day {}", day);
+            cargo(root, &["run", "--release", "--quiet", "--", "run", &day]);
+            println!("=== Testing day {}", day);
+            cargo(root, &["test", "--quiet", "--lib", &test_filter]);
+            println!(
+                "=== Watching {} and {} for changes (Ctrl-C to stop)",
+                files[0].display(),
+                files[1].display()
+            );
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
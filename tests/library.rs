@@ -0,0 +1,87 @@
+//! Checks the building blocks the solutions export work when used from outside the crate.
+
+use aoc2020::day13::{extended_euclidean_algorithm, modulus_inverse, Bezout};
+use aoc2020::day17::Automaton;
+use aoc2020::day18::shunting_yard;
+use aoc2020::day19::Grammar;
+use aoc2020::error::Error;
+use aoc2020::input::{DataDir, Text};
+use aoc2020::runner;
+use aoc2020::util::find_pair;
+
+#[test]
+fn test_find_pair() {
+    assert_eq!(find_pair(&[299, 366, 675, 979, 1456, 1721], 2020), Some((299, 1721)));
+    assert_eq!(find_pair(&[1, 2, 3], 100), None);
+}
+
+#[test]
+fn test_number_theory() {
+    assert_eq!(
+        extended_euclidean_algorithm(240, 46),
+        (2, Bezout { left: -9, right: 47 })
+    );
+    assert_eq!(modulus_inverse(3, 7), 5);
+}
+
+#[test]
+fn test_shunting_yard() {
+    let tokens = || b"2*3+(4*5)".iter().copied();
+    assert_eq!(shunting_yard(tokens(), |_| 0), 26);
+    assert_eq!(shunting_yard(tokens(), |op| (op == b'+') as u8), 46);
+}
+
+#[test]
+fn test_automaton() {
+    let mut automaton = Automaton::<(isize, isize, isize)>::from_slice(&[
+        (1, 0),
+        (2, 1),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+    ]);
+    for _ in 0..6 {
+        automaton.step();
+    }
+    assert_eq!(automaton.count_active(), 112);
+}
+
+#[test]
+fn test_grammar() {
+    let grammar = "0: 1 2\n1: \"a\"\n2: 1 3 | 3 1\n3: \"b\"".parse::<Grammar>().unwrap();
+    assert!(grammar.matches(0, "aab"));
+    assert!(grammar.matches(0, "aba"));
+    assert!(!grammar.matches(0, "abb"));
+
+    let err = "0: 1 2\n1: \"a\"".parse::<Grammar>().err().unwrap();
+    assert_eq!(err.to_string(), "<input>:1: rule 0 refers to missing rule 2");
+}
+
+#[test]
+fn test_run_day() {
+    let provider = Text("1721\n979\n366\n299\n675\n1456".to_string());
+    let solution = aoc2020::solution(1).unwrap();
+    let outcome = runner::run_day(solution, &provider, &[1, 2], 1).unwrap();
+    assert_eq!(*outcome.parts[0].answer.as_ref().unwrap(), 514579);
+    assert_eq!(*outcome.parts[1].answer.as_ref().unwrap(), 241861950);
+
+    let missing = DataDir(std::env::temp_dir().join("aoc2020-no-such-dir"));
+    let err = runner::run_day(solution, &missing, &[1], 1).err().unwrap();
+    assert!(err.is_missing_input());
+}
+
+#[test]
+fn test_run_days_survives_panics() {
+    // Day 1 can't find a pair summing to 2020, so part 1 panics, but part 2 and day 10 still run
+    let provider = Text("1\n2\n4\n2017".to_string());
+    let outcomes = runner::run_days(&[1, 10], &provider, &[1, 2], 1, 2, |_, _| {});
+    assert_eq!(outcomes.len(), 2);
+    let day1 = outcomes[0].1.as_ref().unwrap();
+    match &day1.parts[0].answer {
+        Err(err @ Error::Panic { .. }) => {
+            assert_eq!(err.to_string(), "panicked: No two entries sum to 2020")
+        }
+        _ => panic!("Day 1, part 1 should have panicked"),
+    }
+    assert_eq!(*day1.parts[1].answer.as_ref().unwrap(), 2 * 2017);
+}